@@ -33,15 +33,8 @@ echo "🔨 Building application..."
 source "$HOME/.cargo/env"
 cargo build --release
 
-# Initialize database if needed
-if [ ! -f "/opt/foodie/data/foodie.db" ]; then
-    echo "🗄️  Initializing database..."
-    sqlite3 /opt/foodie/data/foodie.db < "$APP_DIR/schema.sql"
-fi
-
-# Run database migrations (if you add migrations later)
-# echo "🔄 Running database migrations..."
-# sqlx migrate run
+# Database directory (schema migrations are applied by the server on startup)
+mkdir -p /opt/foodie/data
 
 # Restart the service
 echo "🔄 Restarting service..."
@@ -0,0 +1,25 @@
+-- Migration: 알림 시스템
+-- Description: 좋아요, 멘션, 친구 요청/수락 알림 보관함
+
+-- Notifications Table (수신자별 알림)
+-- target: kind에 따라 post_id / comment_id / friend_request_id 중 해당 값 사용
+CREATE TABLE IF NOT EXISTS notifications (
+    id TEXT PRIMARY KEY,
+    recipient_id TEXT NOT NULL,
+    actor_id TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('POST_LIKED', 'COMMENT_MENTION', 'FRIEND_REQUEST_RECEIVED', 'FRIEND_REQUEST_ACCEPTED')),
+    post_id TEXT,
+    comment_id TEXT,
+    friend_request_id TEXT,
+    is_read BOOLEAN DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    read_at DATETIME,
+    FOREIGN KEY (recipient_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (post_id) REFERENCES feed_posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+    FOREIGN KEY (friend_request_id) REFERENCES friend_requests(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_recipient ON notifications(recipient_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(recipient_id, is_read);
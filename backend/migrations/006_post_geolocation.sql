@@ -0,0 +1,10 @@
+-- Migration: 포스트 위치 좌표
+-- Description: 주변 맛집 검색을 위한 위도/경도 (선택 사항)
+
+-- 좌표는 둘 다 있거나 둘 다 없음 (애플리케이션에서 검증)
+ALTER TABLE feed_posts ADD COLUMN latitude REAL;
+ALTER TABLE feed_posts ADD COLUMN longitude REAL;
+
+-- Elasticsearch 장애 시 바운딩 박스 검색용
+CREATE INDEX IF NOT EXISTS idx_feed_posts_lat_lng ON feed_posts(latitude, longitude)
+    WHERE latitude IS NOT NULL AND longitude IS NOT NULL;
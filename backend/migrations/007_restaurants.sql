@@ -0,0 +1,24 @@
+-- Migration: 식당 엔티티
+-- Description: 같은 식당의 리뷰(포스트)를 묶어 평균 평점, 리뷰 수 집계
+
+-- Restaurants Table
+-- normalized_name: 공백/특수문자 제거 + 소문자 (자유 입력 위치 매칭용)
+CREATE TABLE IF NOT EXISTS restaurants (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    normalized_name TEXT NOT NULL,
+    address TEXT,
+    latitude REAL,
+    longitude REAL,
+    categories TEXT NOT NULL DEFAULT '[]', -- JSON array of Category
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_restaurants_normalized_name ON restaurants(normalized_name);
+
+-- 포스트 ↔ 식당 연결 (선택)
+ALTER TABLE feed_posts ADD COLUMN restaurant_id TEXT REFERENCES restaurants(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_feed_posts_restaurant ON feed_posts(restaurant_id, created_at DESC)
+    WHERE restaurant_id IS NOT NULL;
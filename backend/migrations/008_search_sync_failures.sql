@@ -0,0 +1,11 @@
+-- Migration: 검색 인덱스 동기화 실패 기록
+-- Description: Elasticsearch 반영에 실패한 포스트를 기록하고 재시도 (조용히 어긋나지 않도록)
+
+-- 포스트가 삭제된 경우(인덱스 삭제 실패)도 기록하므로 feed_posts FK 없음
+CREATE TABLE IF NOT EXISTS search_sync_failures (
+    post_id TEXT PRIMARY KEY,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    last_error TEXT NOT NULL,
+    first_failed_at DATETIME NOT NULL,
+    last_failed_at DATETIME NOT NULL
+);
@@ -0,0 +1,29 @@
+-- Migration: 검색 인덱싱 아웃박스
+-- Description: 포스트 변경과 같은 트랜잭션에 기록하여 인덱싱 요청이 유실되지 않도록 함
+--              (008의 search_sync_failures를 대체)
+
+-- status: PENDING(처리 대기/재시도 대기), DEAD(재시도 한도 초과, 수동 재시도 필요)
+-- version: 처리 중 같은 포스트가 다시 변경되면 증가 (처리 완료 시 버전이 같을 때만 삭제)
+CREATE TABLE IF NOT EXISTS search_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'DEAD')),
+    version INTEGER NOT NULL DEFAULT 1,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    available_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+-- 포스트당 대기 항목은 하나 (연속 변경은 합쳐짐)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_search_outbox_pending_post ON search_outbox(post_id)
+    WHERE status = 'PENDING';
+CREATE INDEX IF NOT EXISTS idx_search_outbox_due ON search_outbox(status, available_at);
+
+-- 남아있던 실패 기록은 아웃박스로 옮겨 다시 처리
+INSERT INTO search_outbox (post_id, status, attempts, last_error, available_at, created_at, updated_at)
+SELECT post_id, 'PENDING', 0, last_error, CURRENT_TIMESTAMP, first_failed_at, CURRENT_TIMESTAMP
+FROM search_sync_failures;
+
+DROP TABLE IF EXISTS search_sync_failures;
@@ -0,0 +1,34 @@
+-- Migration: SQLite FTS5 게시물 검색 인덱스
+-- Description: Elasticsearch가 없거나 장애일 때 사용하는 전문 검색 (트리거로 feed_posts와 동기화)
+
+-- tags는 JSON 배열 문자열 그대로 저장 (unicode61 토크나이저가 구두점을 구분자로 처리)
+CREATE VIRTUAL TABLE IF NOT EXISTS feed_posts_fts USING fts5(
+    post_id UNINDEXED,
+    title,
+    content,
+    location,
+    tags,
+    tokenize = 'unicode61'
+);
+
+CREATE TRIGGER IF NOT EXISTS feed_posts_fts_insert AFTER INSERT ON feed_posts
+BEGIN
+    INSERT INTO feed_posts_fts (post_id, title, content, location, tags)
+    VALUES (new.id, new.title, new.content, new.location, new.tags);
+END;
+
+CREATE TRIGGER IF NOT EXISTS feed_posts_fts_update AFTER UPDATE OF title, content, location, tags ON feed_posts
+BEGIN
+    DELETE FROM feed_posts_fts WHERE post_id = old.id;
+    INSERT INTO feed_posts_fts (post_id, title, content, location, tags)
+    VALUES (new.id, new.title, new.content, new.location, new.tags);
+END;
+
+CREATE TRIGGER IF NOT EXISTS feed_posts_fts_delete AFTER DELETE ON feed_posts
+BEGIN
+    DELETE FROM feed_posts_fts WHERE post_id = old.id;
+END;
+
+-- 기존 포스트 채우기
+INSERT INTO feed_posts_fts (post_id, title, content, location, tags)
+SELECT id, title, content, location, tags FROM feed_posts;
@@ -0,0 +1,7 @@
+-- Migration: 사용자 닉네임
+-- Description: 사용자 검색용 닉네임 (선택 사항, 카카오 가입 시 카카오 닉네임으로 채움)
+
+ALTER TABLE users ADD COLUMN nickname TEXT;
+
+-- login_method는 기본값이 'kakao'이므로 kakao_id로 판별
+UPDATE users SET nickname = name WHERE kakao_id IS NOT NULL;
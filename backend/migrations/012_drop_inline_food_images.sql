@@ -0,0 +1,7 @@
+-- Migration: 포스트 원본 이미지 데이터 URI 정리
+-- Description: 처리된 이미지(image_urls)가 있는 포스트는 food_image의 base64 원본을 비움
+--              (image_urls가 없는 이전 포스트는 표시할 이미지가 없으므로 유지)
+
+UPDATE feed_posts
+SET food_image = NULL
+WHERE food_image LIKE 'data:%' AND image_urls IS NOT NULL;
@@ -0,0 +1,20 @@
+-- Migration: 직접 업로드 이미지
+-- Description: presigned URL로 저장소에 직접 올린 원본과 처리된 이미지 변형 추적
+--              (PENDING: 업로드 URL 발급, READY: 변형 생성 완료, post_id: 연결된 포스트)
+
+CREATE TABLE IF NOT EXISTS image_uploads (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    original_key TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'PENDING',
+    image_urls TEXT,
+    post_id TEXT,
+    expires_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_uploads_user ON image_uploads(user_id, created_at);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_image_uploads_post ON image_uploads(post_id) WHERE post_id IS NOT NULL;
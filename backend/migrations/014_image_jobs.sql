@@ -0,0 +1,27 @@
+-- Migration: 비동기 이미지 처리 큐
+-- Description: 포스트는 원본만 저장소에 올리고 바로 생성, 변형 생성은 워커가 처리
+--              (image_status: NULL 이미지 없음, PROCESSING 처리 중, READY 완료, FAILED 재시도 한도 초과)
+
+ALTER TABLE feed_posts ADD COLUMN image_status TEXT;
+
+UPDATE feed_posts SET image_status = 'READY'
+WHERE image_urls IS NOT NULL OR food_image IS NOT NULL;
+
+-- status: PENDING(처리 대기/재시도 대기), DEAD(재시도 한도 초과)
+-- available_at: 워커가 가져가면 처리 제한 시간만큼 미뤄서 다른 워커가 중복 처리하지 않도록 함
+CREATE TABLE IF NOT EXISTS image_jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    image_id TEXT NOT NULL,
+    source_key TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'DEAD')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    available_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_image_jobs_post ON image_jobs(post_id);
+CREATE INDEX IF NOT EXISTS idx_image_jobs_due ON image_jobs(status, available_at);
@@ -0,0 +1,9 @@
+-- Migration: 사진 촬영 정보
+-- Description: 사용자가 동의한 경우에만 EXIF의 촬영 시각/좌표를 추천 값으로 보관
+--              (taken_at: 촬영 기기의 현지 시각)
+
+ALTER TABLE image_uploads ADD COLUMN taken_at DATETIME;
+ALTER TABLE image_uploads ADD COLUMN latitude REAL;
+ALTER TABLE image_uploads ADD COLUMN longitude REAL;
+
+ALTER TABLE feed_posts ADD COLUMN taken_at DATETIME;
@@ -0,0 +1,11 @@
+-- Migration: 사용자 검색 인덱싱 아웃박스
+-- Description: 사용자 문서 갱신(가입, 프로필 수정)도 search_outbox로 처리하여 유실되지 않도록 함
+--              (kind로 포스트/사용자 구분, post_id → target_id)
+
+ALTER TABLE search_outbox ADD COLUMN kind TEXT NOT NULL DEFAULT 'POST' CHECK (kind IN ('POST', 'USER'));
+ALTER TABLE search_outbox RENAME COLUMN post_id TO target_id;
+
+-- 대상당 대기 항목은 하나 (연속 변경은 합쳐짐)
+DROP INDEX IF EXISTS idx_search_outbox_pending_post;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_search_outbox_pending_target ON search_outbox(kind, target_id)
+    WHERE status = 'PENDING';
@@ -0,0 +1,34 @@
+-- Migration: 사용자 검색 초성 컬럼
+-- Description: SQLite 사용자 검색에서 초성 일치를 SQL로 먼저 거를 수 있도록 이름/닉네임 초성 저장
+--              (이후에는 가입, 프로필 수정 시 애플리케이션에서 `suggest::chosung`으로 기록)
+
+ALTER TABLE users ADD COLUMN name_chosung TEXT;
+ALTER TABLE users ADD COLUMN nickname_chosung TEXT;
+
+-- 기존 사용자 채우기: 한글 음절(가~힣)은 초성 자모로, 공백은 제거, 나머지는 소문자로
+CREATE TEMP TABLE user_chosung AS
+WITH RECURSIVE chosung(id, field, rest, result) AS (
+    SELECT id, field, value, '' FROM (
+        SELECT id, 'name' AS field, name AS value FROM users
+        UNION ALL
+        SELECT id, 'nickname', nickname FROM users WHERE nickname IS NOT NULL
+    )
+    UNION ALL
+    SELECT id, field, substr(rest, 2), result || CASE
+        WHEN unicode(rest) BETWEEN 44032 AND 55203
+            THEN substr('ㄱㄲㄴㄷㄸㄹㅁㅂㅃㅅㅆㅇㅈㅉㅊㅋㅌㅍㅎ', (unicode(rest) - 44032) / 588 + 1, 1)
+        WHEN substr(rest, 1, 1) IN (' ', char(9), char(10), char(13)) THEN ''
+        ELSE lower(substr(rest, 1, 1))
+    END
+    FROM chosung
+    WHERE rest != ''
+)
+SELECT id, field, result FROM chosung WHERE rest = '';
+
+UPDATE users SET name_chosung = c.result
+FROM user_chosung c WHERE c.id = users.id AND c.field = 'name';
+
+UPDATE users SET nickname_chosung = c.result
+FROM user_chosung c WHERE c.id = users.id AND c.field = 'nickname';
+
+DROP TABLE user_chosung;
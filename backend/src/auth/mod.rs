@@ -11,10 +11,28 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, bcrypt::Bcryp
     verify(password, hash)
 }
 
+/// 관리자 여부 (ADMIN_USER_IDS 환경 변수, 쉼표로 구분된 사용자 ID)
+pub fn is_admin(user_id: &str) -> bool {
+    let admin_ids = std::env::var("ADMIN_USER_IDS").unwrap_or_default();
+    is_listed(&admin_ids, user_id)
+}
+
+fn is_listed(ids: &str, user_id: &str) -> bool {
+    ids.split(',').map(str::trim).any(|id| !id.is_empty() && id == user_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_listed() {
+        assert!(is_listed("user-1, user-2", "user-2"));
+        assert!(!is_listed("user-1,user-2", "user-3"));
+        assert!(!is_listed("", ""));
+        assert!(!is_listed("user-1,,", ""));
+    }
+
     #[test]
     fn test_hash_password() {
         let password = "test_password_123";
@@ -1,11 +1,14 @@
 use redis::{AsyncCommands, RedisError};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 const FRIEND_LIST_PREFIX: &str = "friends:";
 const FRIEND_IDS_PREFIX: &str = "friend_ids:";
 const FRIEND_COUNT_PREFIX: &str = "friend_count:";
+const FRIEND_SUGGESTIONS_PREFIX: &str = "friend_suggestions:";
 const CACHE_TTL: u64 = 3600; // 1시간
+const SUGGESTIONS_CACHE_TTL: u64 = 600; // 10분 (친구의 친구 관계 변화는 무효화되지 않으므로 짧게 유지)
 
 #[derive(Clone)]
 pub struct FriendCache {
@@ -18,6 +21,15 @@ pub struct CachedFriendList {
     pub count: i64,
 }
 
+/// 캐시된 친구 추천 항목 (점수 순으로 정렬된 상태로 저장)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSuggestion {
+    pub user_id: String,
+    pub mutual_friend_count: i64,
+    pub shared_tags: Vec<String>,
+    pub score: f64,
+}
+
 impl FriendCache {
     pub fn new(redis_url: &str) -> Result<Self, RedisError> {
         let redis_client = redis::Client::open(redis_url)?;
@@ -38,6 +50,29 @@ impl FriendCache {
         }
     }
 
+    /// 여러 사용자의 친구 ID 목록을 한 번에 조회 (캐시에 있는 사용자만 반환)
+    pub async fn get_many_friend_ids(&self, user_ids: &[String]) -> Result<HashMap<String, Vec<String>>, RedisError> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let keys: Vec<String> = user_ids
+            .iter()
+            .map(|user_id| format!("{}{}", FRIEND_IDS_PREFIX, user_id))
+            .collect();
+
+        let results: Vec<Option<String>> = conn.mget(&keys).await?;
+        Ok(user_ids
+            .iter()
+            .zip(results)
+            .filter_map(|(user_id, data)| {
+                let friend_ids: Vec<String> = serde_json::from_str(&data?).ok()?;
+                Some((user_id.clone(), friend_ids))
+            })
+            .collect())
+    }
+
     /// 친구 ID 목록 캐시 저장
     pub async fn set_friend_ids(&self, user_id: &str, friend_ids: &[String]) -> Result<(), RedisError> {
         let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
@@ -65,6 +100,34 @@ impl FriendCache {
         Ok(())
     }
 
+    /// 친구 추천 목록 조회
+    pub async fn get_suggestions(&self, user_id: &str) -> Result<Option<Vec<CachedSuggestion>>, RedisError> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", FRIEND_SUGGESTIONS_PREFIX, user_id);
+
+        let result: Option<String> = conn.get(&key).await?;
+        Ok(result.and_then(|data| serde_json::from_str(&data).ok()))
+    }
+
+    /// 친구 추천 목록 캐시 저장
+    pub async fn set_suggestions(&self, user_id: &str, suggestions: &[CachedSuggestion]) -> Result<(), RedisError> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", FRIEND_SUGGESTIONS_PREFIX, user_id);
+        let data = serde_json::to_string(suggestions).unwrap_or_default();
+
+        let _: () = conn.set_ex(&key, data, SUGGESTIONS_CACHE_TTL).await?;
+        Ok(())
+    }
+
+    /// 친구 추천 캐시만 무효화 (친구 요청 전송 시)
+    pub async fn invalidate_suggestions(&self, user_id: &str) -> Result<(), RedisError> {
+        let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+        let key = format!("{}{}", FRIEND_SUGGESTIONS_PREFIX, user_id);
+
+        let _: () = conn.del(&key).await?;
+        Ok(())
+    }
+
     /// 특정 사용자의 캐시 무효화
     pub async fn invalidate_user_cache(&self, user_id: &str) -> Result<(), RedisError> {
         let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
@@ -73,6 +136,7 @@ impl FriendCache {
             format!("{}{}", FRIEND_IDS_PREFIX, user_id),
             format!("{}{}", FRIEND_COUNT_PREFIX, user_id),
             format!("{}{}", FRIEND_LIST_PREFIX, user_id),
+            format!("{}{}", FRIEND_SUGGESTIONS_PREFIX, user_id),
         ];
 
         for key in keys {
@@ -1,3 +1,3 @@
 pub mod friend_cache;
 
-pub use friend_cache::FriendCache;
+pub use friend_cache::{CachedSuggestion, FriendCache};
@@ -0,0 +1,518 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::fmt;
+use std::time::Instant;
+
+/// 버전이 매겨진 마이그레이션 파일
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// 내장 마이그레이션 목록
+///
+/// 새 마이그레이션은 `migrations/NNN_name.sql` 파일로 추가하고 목록 끝에 등록
+/// (버전은 증가하기만 하면 되며 연속일 필요는 없음)
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        sql: include_str!("../../migrations/001_initial_schema.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "kakao_oauth",
+        sql: include_str!("../../migrations/004_kakao_oauth.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "notifications",
+        sql: include_str!("../../migrations/005_notifications.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "post_geolocation",
+        sql: include_str!("../../migrations/006_post_geolocation.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "restaurants",
+        sql: include_str!("../../migrations/007_restaurants.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "search_sync_failures",
+        sql: include_str!("../../migrations/008_search_sync_failures.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "search_outbox",
+        sql: include_str!("../../migrations/009_search_outbox.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "post_search_fts",
+        sql: include_str!("../../migrations/010_post_search_fts.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "user_nickname",
+        sql: include_str!("../../migrations/011_user_nickname.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "drop_inline_food_images",
+        sql: include_str!("../../migrations/012_drop_inline_food_images.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "image_uploads",
+        sql: include_str!("../../migrations/013_image_uploads.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "image_jobs",
+        sql: include_str!("../../migrations/014_image_jobs.sql"),
+    },
+    Migration {
+        version: 15,
+        name: "photo_metadata",
+        sql: include_str!("../../migrations/015_photo_metadata.sql"),
+    },
+    Migration {
+        version: 16,
+        name: "search_outbox_users",
+        sql: include_str!("../../migrations/016_search_outbox_users.sql"),
+    },
+    Migration {
+        version: 17,
+        name: "user_search_chosung",
+        sql: include_str!("../../migrations/017_user_search_chosung.sql"),
+    },
+];
+
+/// 러너 도입 전에 수동으로 적용하던 마이그레이션의 표식 (버전, 테이블, 컬럼)
+///
+/// 적용 기록이 없어도 표식 컬럼이 이미 있으면 다시 실행하지 않고 적용된 것으로 기록
+/// (예: 문서에 따라 `sqlite3 < 004_kakao_oauth.sql`을 직접 실행한 DB)
+const LEGACY_MARKERS: &[(i64, &str, &str)] = &[(4, "users", "login_method")];
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        checksum(self.sql)
+    }
+}
+
+/// 마이그레이션 SQL 체크섬 (SHA-256)
+///
+/// 줄바꿈을 정규화하여 CRLF 체크아웃에서도 같은 값을 반환
+pub fn checksum(sql: &str) -> String {
+    let normalized = sql.replace("\r\n", "\n");
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// schema_migrations 테이블에 기록된 적용 이력
+#[derive(Debug, Clone, FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: DateTime<Utc>,
+    pub execution_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// 적용 완료
+    Applied,
+    /// 아직 적용되지 않음
+    Pending,
+    /// 적용 후 파일 내용이 변경됨
+    ChecksumMismatch,
+    /// DB에는 적용 기록이 있지만 파일이 없음
+    Missing,
+    /// 적용 기록은 없지만 스키마가 이미 있음 (실행하지 않고 적용 기록만 추가)
+    Adoptable,
+}
+
+/// 마이그레이션별 상태
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// 마이그레이션 실행 결과
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    /// 이번 실행에서 새로 적용된 마이그레이션 (버전, 이름, 실행 시간 ms)
+    pub applied: Vec<(i64, String, i64)>,
+    /// 이미 적용되어 있던 마이그레이션 수
+    pub already_applied: usize,
+    /// 스키마가 이미 있어 실행 없이 적용 기록만 추가한 마이그레이션 (버전, 이름)
+    pub adopted: Vec<(i64, String)>,
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    Database(sqlx::Error),
+    /// 적용된 마이그레이션 파일이 수정됨 (새 마이그레이션으로 추가해야 함)
+    ChecksumMismatch { version: i64, name: String },
+    /// 마이그레이션 적용 실패 (해당 트랜잭션은 롤백됨)
+    Failed {
+        version: i64,
+        name: String,
+        source: sqlx::Error,
+    },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Database(e) => write!(f, "Migration database error: {}", e),
+            MigrationError::ChecksumMismatch { version, name } => write!(
+                f,
+                "Migration {:03}_{} was modified after being applied (checksum mismatch)",
+                version, name
+            ),
+            MigrationError::Failed { version, name, source } => write!(
+                f,
+                "Migration {:03}_{} failed and was rolled back: {}",
+                version, name, source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<sqlx::Error> for MigrationError {
+    fn from(e: sqlx::Error) -> Self {
+        MigrationError::Database(e)
+    }
+}
+
+/// 적용 이력과 마이그레이션 목록을 비교하여 상태 계산
+pub fn plan(migrations: &[Migration], applied: &[AppliedMigration]) -> Vec<MigrationStatus> {
+    let mut statuses: Vec<MigrationStatus> = migrations
+        .iter()
+        .map(|migration| {
+            let record = applied.iter().find(|a| a.version == migration.version);
+            let state = match record {
+                Some(a) if a.checksum == migration.checksum() => MigrationState::Applied,
+                Some(_) => MigrationState::ChecksumMismatch,
+                None => MigrationState::Pending,
+            };
+
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                state,
+                applied_at: record.map(|a| a.applied_at),
+            }
+        })
+        .collect();
+
+    for record in applied {
+        if !migrations.iter().any(|m| m.version == record.version) {
+            statuses.push(MigrationStatus {
+                version: record.version,
+                name: record.name.clone(),
+                state: MigrationState::Missing,
+                applied_at: Some(record.applied_at),
+            });
+        }
+    }
+
+    statuses.sort_by_key(|s| s.version);
+    statuses
+}
+
+async fn ensure_migrations_table(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at DATETIME NOT NULL,
+            execution_ms INTEGER NOT NULL
+        )"
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// 현재 데이터베이스의 마이그레이션 상태 조회
+pub async fn status(pool: &SqlitePool) -> Result<Vec<MigrationStatus>, MigrationError> {
+    ensure_migrations_table(pool).await?;
+
+    let applied = sqlx::query_as::<_, AppliedMigration>(
+        "SELECT * FROM schema_migrations ORDER BY version ASC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut statuses = plan(MIGRATIONS, &applied);
+    for status in statuses.iter_mut().filter(|s| s.state == MigrationState::Pending) {
+        if is_legacy_applied(pool, status.version).await? {
+            status.state = MigrationState::Adoptable;
+        }
+    }
+
+    Ok(statuses)
+}
+
+/// 러너 도입 전에 수동으로 적용된 마이그레이션인지 확인 (표식 컬럼 존재 여부)
+async fn is_legacy_applied(pool: &SqlitePool, version: i64) -> Result<bool, sqlx::Error> {
+    let Some((_, table, column)) = LEGACY_MARKERS.iter().find(|(v, _, _)| *v == version) else {
+        return Ok(false);
+    };
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count > 0)
+}
+
+/// 대기 중인 마이그레이션을 버전 순서대로 적용
+pub async fn run(pool: &SqlitePool) -> Result<MigrationReport, MigrationError> {
+    let statuses = status(pool).await?;
+    let mut report = MigrationReport::default();
+
+    for status in &statuses {
+        match status.state {
+            MigrationState::ChecksumMismatch => {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: status.version,
+                    name: status.name.clone(),
+                });
+            }
+            MigrationState::Missing => {
+                log::warn!(
+                    "Migration {:03}_{} is recorded in schema_migrations but its file is missing",
+                    status.version,
+                    status.name
+                );
+            }
+            MigrationState::Applied => report.already_applied += 1,
+            MigrationState::Pending | MigrationState::Adoptable => {}
+        }
+    }
+
+    for migration in MIGRATIONS {
+        let state = statuses
+            .iter()
+            .find(|s| s.version == migration.version)
+            .map(|s| s.state);
+
+        if state == Some(MigrationState::Adoptable) {
+            let mut tx = pool.begin().await?;
+            record(&mut tx, migration, 0).await?;
+            tx.commit().await?;
+
+            log::info!(
+                "Adopted migration {:03}_{} (schema already present, not re-run)",
+                migration.version,
+                migration.name
+            );
+            report.adopted.push((migration.version, migration.name.to_string()));
+        } else if state == Some(MigrationState::Pending) {
+            let execution_ms = apply(pool, migration).await?;
+            log::info!(
+                "Applied migration {:03}_{} ({}ms)",
+                migration.version,
+                migration.name,
+                execution_ms
+            );
+            report
+                .applied
+                .push((migration.version, migration.name.to_string(), execution_ms));
+        }
+    }
+
+    Ok(report)
+}
+
+/// 마이그레이션 하나를 트랜잭션으로 적용하고 이력 기록
+async fn apply(pool: &SqlitePool, migration: &Migration) -> Result<i64, MigrationError> {
+    let started = Instant::now();
+    let mut tx = pool.begin().await?;
+
+    // 여러 문장으로 된 SQL 파일을 그대로 실행 (실패 시 tx drop으로 롤백)
+    sqlx::raw_sql(migration.sql)
+        .execute(&mut *tx)
+        .await
+        .map_err(|source| MigrationError::Failed {
+            version: migration.version,
+            name: migration.name.to_string(),
+            source,
+        })?;
+
+    let execution_ms = started.elapsed().as_millis() as i64;
+    record(&mut tx, migration, execution_ms).await?;
+    tx.commit().await?;
+
+    Ok(execution_ms)
+}
+
+/// 적용 이력 기록
+async fn record(conn: &mut SqliteConnection, migration: &Migration, execution_ms: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO schema_migrations (version, name, checksum, applied_at, execution_ms)
+         VALUES (?, ?, ?, ?, ?)"
+    )
+    .bind(migration.version)
+    .bind(migration.name)
+    .bind(migration.checksum())
+    .bind(Utc::now())
+    .bind(execution_ms)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(version: i64, checksum: &str) -> AppliedMigration {
+        AppliedMigration {
+            version,
+            name: format!("m{}", version),
+            checksum: checksum.to_string(),
+            applied_at: Utc::now(),
+            execution_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_migration_versions_are_increasing() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+    }
+
+    #[test]
+    fn test_checksum_ignores_line_endings() {
+        assert_eq!(checksum("SELECT 1;\nSELECT 2;"), checksum("SELECT 1;\r\nSELECT 2;"));
+        assert_ne!(checksum("SELECT 1;"), checksum("SELECT 2;"));
+    }
+
+    #[test]
+    fn test_plan_states() {
+        let migrations = [
+            Migration { version: 1, name: "one", sql: "SELECT 1;" },
+            Migration { version: 2, name: "two", sql: "SELECT 2;" },
+            Migration { version: 3, name: "three", sql: "SELECT 3;" },
+        ];
+        let records = vec![
+            applied(1, &checksum("SELECT 1;")),
+            applied(2, "stale"),
+            applied(9, "removed"),
+        ];
+
+        let states: Vec<_> = plan(&migrations, &records)
+            .into_iter()
+            .map(|s| (s.version, s.state))
+            .collect();
+
+        assert_eq!(
+            states,
+            vec![
+                (1, MigrationState::Applied),
+                (2, MigrationState::ChecksumMismatch),
+                (3, MigrationState::Pending),
+                (9, MigrationState::Missing),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_on_fresh_database_is_idempotent() {
+        // 인메모리 DB는 커넥션마다 분리되므로 커넥션 1개로 제한
+        let pool = crate::db::test_support::empty_pool().await;
+
+        let report = run(&pool).await.unwrap();
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+
+        let report = run(&pool).await.unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(report.already_applied, MIGRATIONS.len());
+
+        // 004_kakao_oauth 적용 확인
+        let kakao_columns: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('users') WHERE name IN ('login_method', 'kakao_id')"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(kakao_columns, 2);
+    }
+
+    #[tokio::test]
+    async fn test_user_search_chosung_backfill() {
+        let pool = crate::db::test_support::empty_pool().await;
+        let (last, earlier) = MIGRATIONS.split_last().unwrap();
+        assert_eq!(last.name, "user_search_chosung");
+        for migration in earlier {
+            sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+        }
+
+        let users = [("u1", "김 민수", Some("Minsu 팬")), ("u2", "Alice", None), ("u3", "", None)];
+        for (id, name, nickname) in users {
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, name, nickname, initial)
+                 VALUES (?, ?, 'x', ?, ?, 'U')"
+            )
+            .bind(id)
+            .bind(format!("{id}@example.com"))
+            .bind(name)
+            .bind(nickname)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::raw_sql(last.sql).execute(&pool).await.unwrap();
+
+        // 백필 결과가 애플리케이션의 chosung()과 같아야 함
+        for (id, name, nickname) in users {
+            let stored: (Option<String>, Option<String>) = sqlx::query_as(
+                "SELECT name_chosung, nickname_chosung FROM users WHERE id = ?"
+            )
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(stored.0, Some(crate::search::suggest::chosung(name)));
+            assert_eq!(stored.1, nickname.map(crate::search::suggest::chosung));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_adopts_manually_applied_migration() {
+        let pool = crate::db::test_support::empty_pool().await;
+
+        // 러너 도입 전: schema.sql 실행 후 004를 직접 적용한 DB
+        for migration in &MIGRATIONS[..2] {
+            sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+        }
+
+        let states: Vec<_> = status(&pool).await.unwrap().into_iter().map(|s| (s.version, s.state)).collect();
+        assert_eq!(states[..2], [(1, MigrationState::Pending), (4, MigrationState::Adoptable)]);
+
+        let report = run(&pool).await.unwrap();
+        assert_eq!(report.adopted, vec![(4, "kakao_oauth".to_string())]);
+        assert_eq!(report.applied.len(), MIGRATIONS.len() - 1);
+        assert!(status(&pool).await.unwrap().iter().all(|s| s.state == MigrationState::Applied));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod migrations;
+pub mod retry;
+/// 테스트 공용 도구 (인메모리 DB)
+#[cfg(test)]
+pub(crate) mod test_support;
+
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
 use std::env;
 
+use migrations::{MigrationError, MigrationState};
+
 pub async fn create_pool() -> Result<SqlitePool, sqlx::Error> {
     let database_url = env::var("DATABASE_URL")
         .unwrap_or_else(|_| "sqlite:foodie.db".to_string());
@@ -13,20 +21,44 @@ pub async fn create_pool() -> Result<SqlitePool, sqlx::Error> {
     Ok(pool)
 }
 
-pub async fn init_db(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    let schema = include_str!("../../schema.sql");
+/// 대기 중인 스키마 마이그레이션을 적용하고 상태를 기록
+pub async fn init_db(pool: &SqlitePool) -> Result<(), MigrationError> {
+    let report = migrations::run(pool).await?;
+
+    log::info!(
+        "Database migrations complete: {} applied, {} adopted, {} already up to date",
+        report.applied.len(),
+        report.adopted.len(),
+        report.already_applied
+    );
+
+    for status in migrations::status(pool).await? {
+        if status.state != MigrationState::Applied {
+            log::warn!(
+                "Migration {:03}_{} status: {:?}",
+                status.version,
+                status.name,
+                status.state
+            );
+        }
+    }
+
+    Ok(())
+}
 
-    // Execute each statement separately
-    let statements: Vec<&str> = schema
-        .split(';')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect();
+/// 마이그레이션 상태 출력 (`--migration-status`, 적용하지 않고 조회만)
+pub async fn print_migration_status(pool: &SqlitePool) -> Result<(), MigrationError> {
+    println!("{:<8} {:<32} {:<18} APPLIED AT", "VERSION", "NAME", "STATE");
 
-    for statement in statements {
-        sqlx::query(statement).execute(pool).await?;
+    for status in migrations::status(pool).await? {
+        println!(
+            "{:<8} {:<32} {:<18} {}",
+            format!("{:03}", status.version),
+            status.name,
+            format!("{:?}", status.state),
+            status.applied_at.map(|t| t.to_rfc3339()).unwrap_or_else(|| "-".to_string())
+        );
     }
 
-    log::info!("Database initialized successfully");
     Ok(())
 }
@@ -0,0 +1,82 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+/// 첫 재시도 대기 시간
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+/// 최대 재시도 대기 시간
+const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+/// n번째 실패 후 재시도까지 대기 시간 (지수 백오프, 최대 10분)
+pub fn backoff(attempts: i64) -> Duration {
+    let exponent = attempts.clamp(1, 20) as u32 - 1;
+    BASE_BACKOFF
+        .checked_mul(2u32.saturating_pow(exponent))
+        .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF))
+}
+
+/// 재시도 대기열 테이블 (검색 아웃박스, 이미지 작업)
+///
+/// 공통 컬럼: status ('PENDING' / 'DEAD'), attempts, last_error, available_at, updated_at
+pub struct RetryQueue {
+    pub table: &'static str,
+    /// 이 횟수만큼 실패하면 DEAD 처리
+    pub max_attempts: i64,
+}
+
+impl RetryQueue {
+    /// 처리 실패 기록 (백오프 후 재시도, 한도 초과 시 DEAD)
+    ///
+    /// `version`이 있으면 그 버전일 때만 기록하며, 기록했으면 DEAD 여부, 버전이 바뀌었으면 None
+    pub async fn fail(
+        &self,
+        pool: &SqlitePool,
+        id: i64,
+        version: Option<i64>,
+        attempts: i64,
+        error: &str,
+    ) -> Result<Option<bool>, sqlx::Error> {
+        let attempts = attempts + 1;
+        let now = Utc::now();
+        let dead = attempts >= self.max_attempts;
+        let available_at = now
+            + chrono::Duration::from_std(backoff(attempts)).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let query = format!(
+            "UPDATE {}
+             SET status = ?, attempts = ?, last_error = ?, available_at = ?, updated_at = ?
+             WHERE id = ?{}",
+            self.table,
+            if version.is_some() { " AND version = ?" } else { "" }
+        );
+
+        let mut query_builder = sqlx::query(&query)
+            .bind(if dead { "DEAD" } else { "PENDING" })
+            .bind(attempts)
+            .bind(error)
+            .bind(available_at)
+            .bind(now)
+            .bind(id);
+        if let Some(version) = version {
+            query_builder = query_builder.bind(version);
+        }
+
+        let updated = query_builder.execute(pool).await?.rows_affected();
+
+        Ok((updated > 0).then_some(dead))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(2), Duration::from_secs(4));
+        assert_eq!(backoff(5), Duration::from_secs(32));
+        assert_eq!(backoff(12), MAX_BACKOFF);
+        assert_eq!(backoff(i64::MAX), MAX_BACKOFF);
+    }
+}
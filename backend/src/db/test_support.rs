@@ -0,0 +1,17 @@
+use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+
+/// 빈 인메모리 DB (연결을 하나로 제한해야 모든 쿼리가 같은 DB를 봄)
+pub async fn empty_pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+/// 모든 마이그레이션을 적용한 인메모리 DB
+pub async fn test_pool() -> SqlitePool {
+    let pool = empty_pool().await;
+    super::migrations::run(&pool).await.unwrap();
+    pool
+}
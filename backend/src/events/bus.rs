@@ -0,0 +1,205 @@
+use futures_util::{Stream, StreamExt};
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, RedisError};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use uuid::Uuid;
+
+use super::AppEvent;
+
+const EVENT_CHANNEL: &str = "foodie:events";
+const BUS_CAPACITY: usize = 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// 다른 인스턴스로의 발행 제한 시간 (Redis가 느려도 발행한 요청을 막지 않음)
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 인스턴스 간에 전달되는 이벤트 (발행한 인스턴스 ID 포함)
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    origin: String,
+    event: AppEvent,
+}
+
+/// 프로세스 내부 이벤트 버스
+///
+/// Redis 브리지가 연결되면 다른 서버 인스턴스에서 발행된 이벤트도 로컬 구독자에게 전달
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<AppEvent>,
+    instance_id: String,
+    /// 발행용 공유 연결 (끊기면 자동 재연결)
+    redis: Option<ConnectionManager>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// 로컬 전용 이벤트 버스
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BUS_CAPACITY);
+        Self {
+            sender,
+            instance_id: Uuid::new_v4().to_string(),
+            redis: None,
+        }
+    }
+
+    /// Redis pub/sub 브리지가 연결된 이벤트 버스
+    pub async fn with_redis(redis_url: &str) -> Result<Self, RedisError> {
+        let redis_client = redis::Client::open(redis_url)?;
+        let connection = ConnectionManager::new(redis_client.clone()).await?;
+        let mut bus = Self::new();
+
+        tokio::spawn(run_bridge(
+            redis_client,
+            bus.sender.clone(),
+            bus.instance_id.clone(),
+        ));
+
+        bus.redis = Some(connection);
+        Ok(bus)
+    }
+
+    /// 이벤트 발행 (로컬 구독자 + 다른 인스턴스)
+    ///
+    /// 로컬 구독자에게 먼저 전달하고, 다른 인스턴스로의 발행은 기다리지 않음
+    pub fn publish(&self, event: AppEvent) {
+        if let Some(connection) = &self.redis {
+            let envelope = Envelope {
+                origin: self.instance_id.clone(),
+                event: event.clone(),
+            };
+            let connection = connection.clone();
+
+            tokio::spawn(async move {
+                match tokio::time::timeout(PUBLISH_TIMEOUT, publish_remote(connection, &envelope)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => log::warn!("Failed to publish event to Redis: {}", e),
+                    Err(_) => log::warn!("Timed out publishing event to Redis"),
+                }
+            });
+        }
+
+        // 구독자가 없으면 Err이 반환되므로 무시
+        let _ = self.sender.send(event);
+    }
+
+    /// 이벤트 스트림 구독
+    pub fn subscribe(&self) -> impl Stream<Item = AppEvent> {
+        BroadcastStream::new(self.sender.subscribe()).filter_map(|result| async move {
+            match result {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    log::warn!("Subscriber lagged behind event bus: {}", e);
+                    None
+                }
+            }
+        })
+    }
+}
+
+async fn publish_remote(mut connection: ConnectionManager, envelope: &Envelope) -> Result<(), RedisError> {
+    let payload = serde_json::to_string(envelope).unwrap_or_default();
+
+    connection.publish(EVENT_CHANNEL, payload).await
+}
+
+/// Redis 채널을 구독하여 다른 인스턴스의 이벤트를 로컬 버스로 전달 (연결 끊김 시 재시도)
+async fn run_bridge(redis_client: redis::Client, sender: broadcast::Sender<AppEvent>, instance_id: String) {
+    loop {
+        if let Err(e) = listen(&redis_client, &sender, &instance_id).await {
+            log::warn!("Event bridge disconnected from Redis: {}", e);
+        }
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn listen(
+    redis_client: &redis::Client,
+    sender: &broadcast::Sender<AppEvent>,
+    instance_id: &str,
+) -> Result<(), RedisError> {
+    let mut pubsub = redis_client.get_async_pubsub().await?;
+    pubsub.subscribe(EVENT_CHANNEL).await?;
+
+    log::info!("Event bridge subscribed to Redis channel {}", EVENT_CHANNEL);
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let payload: String = message.get_payload()?;
+
+        if let Some(event) = decode_remote(&payload, instance_id) {
+            let _ = sender.send(event);
+        }
+    }
+
+    Ok(())
+}
+
+/// 다른 인스턴스가 발행한 이벤트만 반환 (자신이 발행한 이벤트는 이미 로컬로 전달됨)
+fn decode_remote(payload: &str, instance_id: &str) -> Option<AppEvent> {
+    match serde_json::from_str::<Envelope>(payload) {
+        Ok(envelope) if envelope.origin != instance_id => Some(envelope.event),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("Ignoring malformed event payload: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PostLikeCount;
+
+    fn like_event(likes: i64) -> AppEvent {
+        AppEvent::PostLikeCountChanged(PostLikeCount {
+            post_id: "post-1".to_string(),
+            likes,
+        })
+    }
+
+    fn encode(origin: &str, event: AppEvent) -> String {
+        serde_json::to_string(&Envelope {
+            origin: origin.to_string(),
+            event,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_remote_skips_own_events() {
+        assert!(decode_remote(&encode("me", like_event(1)), "me").is_none());
+
+        match decode_remote(&encode("other", like_event(3)), "me") {
+            Some(AppEvent::PostLikeCountChanged(count)) => assert_eq!(count.likes, 3),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_remote_ignores_malformed_payload() {
+        assert!(decode_remote("not json", "me").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_local_publish_reaches_subscribers() {
+        let bus = EventBus::new();
+        let mut events = Box::pin(bus.subscribe());
+
+        bus.publish(like_event(7));
+
+        match events.next().await {
+            Some(AppEvent::PostLikeCountChanged(count)) => assert_eq!(count.likes, 7),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+}
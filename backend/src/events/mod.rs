@@ -0,0 +1,17 @@
+pub mod bus;
+
+pub use bus::EventBus;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Comment, FriendRequest, PostImageStatus, PostLikeCount};
+
+/// 구독으로 전달되는 실시간 이벤트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum AppEvent {
+    CommentAdded(Comment),
+    PostLikeCountChanged(PostLikeCount),
+    FriendRequestReceived(FriendRequest),
+    PostImageStatusChanged(PostImageStatus),
+}
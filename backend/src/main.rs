@@ -1,6 +1,7 @@
 mod auth;
 mod cache;
 mod db;
+mod events;
 mod models;
 mod schema;
 mod search;
@@ -9,21 +10,27 @@ mod storage;
 
 use actix_cors::Cors;
 use actix_web::{guard, web, App, HttpRequest, HttpResponse, HttpServer, Result};
-use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig, MultipartOptions};
+use async_graphql::Data;
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
 use dotenv::dotenv;
 use std::env;
 
 use crate::auth::jwt::verify_jwt;
 use crate::cache::FriendCache;
-use crate::db::{create_pool, init_db};
+use crate::db::{create_pool, init_db, print_migration_status};
+use crate::events::EventBus;
 use crate::schema::{create_schema, AppSchema};
-use crate::search::{ElasticsearchClient, SearchService};
+use crate::search::sqlite::SqliteSearch;
+use crate::search::{ElasticsearchClient, SearchBackendKind, SearchIndexer, SearchRouter, SearchService, UserSearchService};
 use crate::session::{middleware, RedisSessionStore};
-use crate::storage::S3Client;
+use crate::storage::upload::max_image_bytes;
+use crate::storage::{ImageWorker, LocalStorage, S3Client, Storage, StorageBackendKind};
 
 async fn graphql_playground() -> Result<HttpResponse> {
-    let source = playground_source(GraphQLPlaygroundConfig::new("/graphql"));
+    let source = playground_source(
+        GraphQLPlaygroundConfig::new("/graphql").subscription_endpoint("/graphql"),
+    );
     Ok(HttpResponse::Ok()
         .content_type("text/html; charset=utf-8")
         .body(source))
@@ -58,6 +65,47 @@ async fn graphql_handler(
     schema.execute(request).await.into()
 }
 
+/// graphql-ws 연결 초기화 시 connection_init payload의 토큰으로 사용자 인증
+///
+/// 토큰이 없거나 유효하지 않으면 익명 연결로 처리 (friendRequestReceived 구독만 인증 필요)
+async fn on_ws_connection_init(
+    session_store: RedisSessionStore,
+    payload: serde_json::Value,
+) -> async_graphql::Result<Data> {
+    let mut data = Data::default();
+
+    let token = ["Authorization", "authorization", "token"]
+        .iter()
+        .find_map(|key| payload.get(*key).and_then(|value| value.as_str()))
+        .map(|value| value.strip_prefix("Bearer ").unwrap_or(value).to_string());
+
+    if let Some(token) = token {
+        // 세션 기반 인증 (우선 순위)
+        if let Ok(user_id) = middleware::verify_session(&session_store, &token).await {
+            data.insert(user_id);
+        }
+        // JWT 토큰 폴백 (하위 호환성)
+        else if let Ok(claims) = verify_jwt(&token) {
+            data.insert(claims.sub);
+        }
+    }
+
+    Ok(data)
+}
+
+async fn graphql_ws_handler(
+    schema: web::Data<AppSchema>,
+    session_store: web::Data<RedisSessionStore>,
+    req: HttpRequest,
+    payload: web::Payload,
+) -> Result<HttpResponse> {
+    let session_store = session_store.get_ref().clone();
+
+    GraphQLSubscription::new(AppSchema::clone(&*schema))
+        .on_connection_init(move |value| on_ws_connection_init(session_store, value))
+        .start(&req, payload)
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // 환경 변수 로드
@@ -77,6 +125,14 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to create database pool");
 
+    // 적용된/대기 중인 마이그레이션만 출력하고 종료
+    if env::args().any(|arg| arg == "--migration-status") {
+        print_migration_status(&pool)
+            .await
+            .expect("Failed to read migration status");
+        return Ok(());
+    }
+
     init_db(&pool)
         .await
         .expect("Failed to initialize database");
@@ -110,24 +166,98 @@ async fn main() -> std::io::Result<()> {
     let es_client = ElasticsearchClient::new(&es_url, &es_index)
         .expect("Failed to create Elasticsearch client");
 
+    // 인덱스는 버전별(`{index}_v{n}`)로 만들고 읽기/쓰기는 alias로만 접근
+    // (alias가 없으면 인덱싱 워커가 시작 시 SQLite에서 재인덱싱하여 생성)
     let search_service = SearchService::new(es_client);
 
-    // Elasticsearch 인덱스 생성 (이미 존재하면 무시됨)
-    if let Err(e) = search_service.create_index().await {
-        log::warn!("Failed to create Elasticsearch index (may already exist): {}", e);
-    } else {
-        log::info!("Elasticsearch index initialized successfully");
-    }
+    // 사용자 검색 인덱스 (이름, 닉네임, 초성)
+    let es_users_index = env::var("ELASTICSEARCH_USERS_INDEX").unwrap_or_else(|_| "foodie_users".to_string());
+    let user_search_service = UserSearchService::new(
+        ElasticsearchClient::new(&es_url, &es_users_index)
+            .expect("Failed to create Elasticsearch client"),
+    );
 
-    // S3 클라이언트 초기화 (이미지 저장)
-    let s3_client = S3Client::new()
+    // 검색 백엔드 선택 (auto: Elasticsearch 장애 시 SQLite FTS5로 자동 전환)
+    let search_backend = SearchBackendKind::parse(&env::var("SEARCH_BACKEND").unwrap_or_default())
+        .expect("SEARCH_BACKEND must be one of: auto, elasticsearch, sqlite");
+    let search_router = SearchRouter::new(
+        search_backend,
+        search_service.clone(),
+        user_search_service.clone(),
+        SqliteSearch::new(pool.clone()),
+    );
+    search_router.start_health_check();
+
+    log::info!("Search backend: {:?}", search_backend);
+
+    // 이미지 저장소 (s3 또는 local, 미지정 시 AWS_S3_BUCKET이 있으면 s3)
+    let storage_backend = StorageBackendKind::parse(
+        &env::var("STORAGE_BACKEND").unwrap_or_default(),
+        env::var("AWS_S3_BUCKET").is_ok(),
+    )
+    .expect("STORAGE_BACKEND must be one of: s3, local");
+
+    let storage = match storage_backend {
+        StorageBackendKind::S3 => Storage::S3(
+            S3Client::new()
+                .await
+                .expect("Failed to create S3 client"),
+        ),
+        StorageBackendKind::Local => {
+            let root = env::var("LOCAL_STORAGE_DIR").unwrap_or_else(|_| "./data/uploads".to_string());
+            let public_url = env::var("LOCAL_STORAGE_URL")
+                .unwrap_or_else(|_| format!("http://{}:{}{}", host, port, LocalStorage::ROUTE));
+            // 업로드 URL 서명 키 (여러 인스턴스가 같은 키를 써야 다른 인스턴스가 발급한 URL도 검증됨)
+            let signing_key = env::var("LOCAL_STORAGE_SIGNING_KEY")
+                .expect("LOCAL_STORAGE_SIGNING_KEY must be set for local storage");
+            Storage::Local(
+                LocalStorage::new(root, &public_url, signing_key.as_bytes())
+                    .expect("LOCAL_STORAGE_SIGNING_KEY must be at least 32 bytes"),
+            )
+        }
+    };
+
+    log::info!("Image storage: {:?}", storage_backend);
+
+    // GraphQL multipart 업로드 (포스트당 이미지 1개, 최대 크기 초과 시 요청 거부)
+    let multipart_options = MultipartOptions::default()
+        .max_file_size(max_image_bytes())
+        .max_num_files(1);
+
+    // 실시간 이벤트 버스 (Redis pub/sub으로 인스턴스 간 전달)
+    let event_bus = EventBus::with_redis(&redis_url)
         .await
-        .expect("Failed to create S3 client");
+        .expect("Failed to create event bus");
 
-    log::info!("S3 client initialized successfully");
+    log::info!("Event bus initialized successfully");
+
+    // 검색 인덱스 동기화 워커 (포스트, 사용자 변경을 디바운스 후 반영, 실패 시 재시도)
+    // 포스트, 사용자 검색 alias가 없으면 SQLite에서 재인덱싱하여 생성 (실패 시 다음 주기에 재시도)
+    // SQLite 모드에서는 FTS5 인덱스가 트리거로 동기화되므로 실행하지 않음 (아웃박스는 남아 있다가 전환 시 반영)
+    let search_indexer = if search_backend == SearchBackendKind::Sqlite {
+        None
+    } else {
+        log::info!("Search indexer started");
+        Some(SearchIndexer::start(pool.clone(), search_service.clone(), user_search_service))
+    };
+
+    // 이미지 처리 워커 (포스트 이미지 변형 생성, 실패 시 재시도)
+    let image_worker = ImageWorker::start(pool.clone(), storage.clone(), event_bus.clone());
+    log::info!("Image worker started");
+
+    // 버려진 이미지 정리 (만료된 미확정 업로드, 포스트에 쓰이지 않은 업로드, 오래된 DEAD 작업의 원본)
+    crate::storage::sweeper::start(pool.clone(), storage.clone());
 
     // GraphQL 스키마 생성
-    let schema = create_schema();
+    let schema = create_schema(
+        pool.clone(),
+        event_bus,
+        friend_cache.clone(),
+        search_router,
+        search_indexer,
+        storage.clone(),
+        image_worker,
+    );
 
     log::info!("GraphQL Server running at http://{}:{}", host, port);
     log::info!("GraphQL Playground: http://{}:{}/playground", host, port);
@@ -141,20 +271,43 @@ async fn main() -> std::io::Result<()> {
             .allow_any_header()
             .max_age(3600);
 
-        App::new()
+        let app = App::new()
             .wrap(cors)
             .app_data(web::Data::new(schema.clone()))
             .app_data(web::Data::new(pool.clone()))
             .app_data(web::Data::new(session_store.clone()))
             .app_data(web::Data::new(search_service.clone()))
             .app_data(web::Data::new(friend_cache.clone()))
-            .app_data(web::Data::new(s3_client.clone()))
+            .app_data(multipart_options.clone())
             .service(
                 web::resource("/graphql")
                     .guard(guard::Post())
                     .to(graphql_handler),
             )
-            .service(web::resource("/playground").guard(guard::Get()).to(graphql_playground))
+            .service(
+                web::resource("/graphql")
+                    .guard(guard::Get())
+                    .guard(guard::Header("upgrade", "websocket"))
+                    .to(graphql_ws_handler),
+            )
+            .service(web::resource("/playground").guard(guard::Get()).to(graphql_playground));
+
+        // 로컬 저장소의 이미지 변형 제공 (원본 제외) + 서명된 URL로 직접 업로드 (PUT)
+        match &storage {
+            Storage::Local(local) => app
+                .app_data(web::Data::new(local.clone()))
+                .service(
+                    web::resource(format!("{}/{{key:.*}}", LocalStorage::ROUTE))
+                        .guard(guard::Put())
+                        .app_data(web::PayloadConfig::new(max_image_bytes()))
+                        .to(crate::storage::local::put_object),
+                )
+                .service(
+                    actix_files::Files::new(LocalStorage::ROUTE, local.root())
+                        .path_filter(|path, _| LocalStorage::is_public(path)),
+                ),
+            Storage::S3(_) => app,
+        }
     })
     .bind((host, port))?
     .run()
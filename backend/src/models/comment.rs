@@ -1,8 +1,11 @@
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::schema::loaders::{MentionsLoader, UserLoader};
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Comment {
     pub id: String,
@@ -22,14 +25,9 @@ impl Comment {
     }
 
     async fn author(&self, ctx: &Context<'_>) -> Result<crate::models::User> {
-        let pool = ctx.data::<sqlx::SqlitePool>()?;
-        let user = sqlx::query_as::<_, crate::models::User>(
-            "SELECT * FROM users WHERE id = ?"
-        )
-        .bind(&self.author_id)
-        .fetch_one(pool)
-        .await?;
-        Ok(user)
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let user = loader.load_one(self.author_id.clone()).await?;
+        user.ok_or_else(|| "Author not found".into())
     }
 
     async fn content(&self) -> &str {
@@ -56,16 +54,9 @@ impl Comment {
     }
 
     async fn mentions(&self, ctx: &Context<'_>) -> Result<Vec<crate::models::User>> {
-        let pool = ctx.data::<sqlx::SqlitePool>()?;
-        let users = sqlx::query_as::<_, crate::models::User>(
-            "SELECT u.* FROM users u
-             INNER JOIN comment_mentions cm ON u.id = cm.mentioned_user_id
-             WHERE cm.comment_id = ?"
-        )
-        .bind(&self.id)
-        .fetch_all(pool)
-        .await?;
-        Ok(users)
+        let loader = ctx.data::<DataLoader<MentionsLoader>>()?;
+        let users = loader.load_one(self.id.clone()).await?;
+        Ok(users.unwrap_or_default())
     }
 
     async fn is_reply(&self) -> bool {
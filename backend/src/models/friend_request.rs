@@ -1,8 +1,11 @@
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::schema::loaders::UserLoader;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
 #[sqlx(type_name = "TEXT")]
 pub enum FriendRequestStatus {
@@ -33,21 +36,15 @@ impl FriendRequest {
     }
 
     async fn requester(&self, ctx: &Context<'_>) -> Result<crate::models::User> {
-        let pool = ctx.data::<sqlx::SqlitePool>()?;
-        let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = ?")
-            .bind(&self.requester_id)
-            .fetch_one(pool)
-            .await?;
-        Ok(user)
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let user = loader.load_one(self.requester_id.clone()).await?;
+        user.ok_or_else(|| "User not found".into())
     }
 
     async fn addressee(&self, ctx: &Context<'_>) -> Result<crate::models::User> {
-        let pool = ctx.data::<sqlx::SqlitePool>()?;
-        let user = sqlx::query_as::<_, crate::models::User>("SELECT * FROM users WHERE id = ?")
-            .bind(&self.addressee_id)
-            .fetch_one(pool)
-            .await?;
-        Ok(user)
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let user = loader.load_one(self.addressee_id.clone()).await?;
+        user.ok_or_else(|| "User not found".into())
     }
 
     async fn status(&self) -> FriendRequestStatus {
@@ -63,6 +60,50 @@ impl FriendRequest {
     }
 }
 
+/// 조회 사용자와 차단 관계(어느 쪽이 차단했든)에 있는 사용자 ID 서브쿼리
+///
+/// 바인딩: 조회 사용자 ID 2회 (NULL이면 빈 결과이므로 비로그인 조회에도 사용 가능)
+pub const BLOCKED_USER_IDS_SQL: &str =
+    "SELECT addressee_id FROM friend_requests WHERE requester_id = ? AND status = 'BLOCKED'
+     UNION
+     SELECT requester_id FROM friend_requests WHERE addressee_id = ? AND status = 'BLOCKED'";
+
+impl FriendRequest {
+    /// 두 사용자 사이에 차단 관계가 있는지 확인 (양방향)
+    pub async fn is_blocked_between(
+        pool: &sqlx::SqlitePool,
+        user_id: &str,
+        other_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let blocked: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM friend_requests
+             WHERE status = 'BLOCKED'
+             AND ((requester_id = ? AND addressee_id = ?) OR (requester_id = ? AND addressee_id = ?))
+             LIMIT 1"
+        )
+        .bind(user_id)
+        .bind(other_id)
+        .bind(other_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(blocked.is_some())
+    }
+
+    /// 사용자와 차단 관계에 있는 사용자 ID 목록 (양방향)
+    pub async fn find_blocked_user_ids(
+        pool: &sqlx::SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(BLOCKED_USER_IDS_SQL)
+            .bind(user_id)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct FriendStats {
     pub user_id: String,
@@ -0,0 +1,386 @@
+use async_graphql::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::cache::{CachedSuggestion, FriendCache};
+use crate::models::Friendship;
+
+/// 공통 친구 1명당 점수
+///
+/// 취향 점수 최대치(CATEGORY_WEIGHT + TAG_WEIGHT * MAX_SHARED_TAGS = 4)보다 크게 두어
+/// 공통 친구 수가 우선 순위를 결정하고 취향은 같은 공통 친구 수 안에서 순서를 정함
+const MUTUAL_FRIEND_WEIGHT: f64 = 5.0;
+/// 좋아요한 카테고리 분포 유사도(0~1)에 곱하는 점수
+const CATEGORY_WEIGHT: f64 = 2.0;
+/// 공통 태그 1개당 점수 (최대 MAX_SHARED_TAGS개까지 반영)
+const TAG_WEIGHT: f64 = 0.4;
+const MAX_SHARED_TAGS: usize = 5;
+/// 취향 비교에 사용하는 최근 좋아요 수
+const RECENT_LIKES_LIMIT: i64 = 200;
+/// 취향 기반 후보 수 상한
+const TASTE_CANDIDATES_LIMIT: i64 = 200;
+/// 캐시에 저장하는 추천 수
+pub const MAX_SUGGESTIONS: usize = 50;
+
+/// 친구 추천 결과
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FriendSuggestion {
+    pub user: crate::models::User,
+    /// 공통 친구 수
+    pub mutual_friend_count: i64,
+    /// 둘 다 좋아요한 포스트의 공통 태그
+    pub shared_tags: Vec<String>,
+    pub score: f64,
+}
+
+/// 좋아요한 포스트로 만든 취향 프로필 (카테고리/태그별 좋아요 수)
+#[derive(Debug, Default, Clone)]
+pub struct TasteProfile {
+    categories: HashMap<String, f64>,
+    tags: HashMap<String, f64>,
+}
+
+impl TasteProfile {
+    /// 좋아요한 포스트 하나 반영 (tags는 feed_posts.tags의 JSON 문자열)
+    pub fn add_like(&mut self, category: &str, tags_json: &str) {
+        *self.categories.entry(category.to_string()).or_default() += 1.0;
+
+        let tags: Vec<String> = serde_json::from_str(tags_json).unwrap_or_default();
+        for tag in tags {
+            *self.tags.entry(tag).or_default() += 1.0;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    pub fn category_names(&self) -> Vec<String> {
+        self.categories.keys().cloned().collect()
+    }
+
+    /// 카테고리 분포 코사인 유사도 (0~1)
+    pub fn category_similarity(&self, other: &TasteProfile) -> f64 {
+        let dot: f64 = self
+            .categories
+            .iter()
+            .filter_map(|(category, count)| other.categories.get(category).map(|o| count * o))
+            .sum();
+
+        let norm = |map: &HashMap<String, f64>| map.values().map(|v| v * v).sum::<f64>().sqrt();
+        let denominator = norm(&self.categories) * norm(&other.categories);
+
+        if denominator == 0.0 {
+            0.0
+        } else {
+            dot / denominator
+        }
+    }
+
+    /// 공통 태그 (두 사용자의 좋아요 수 합이 큰 순, 같으면 이름 순)
+    pub fn shared_tags(&self, other: &TasteProfile) -> Vec<String> {
+        let mut shared: Vec<(&String, f64)> = self
+            .tags
+            .iter()
+            .filter_map(|(tag, count)| other.tags.get(tag).map(|o| (tag, count + o)))
+            .collect();
+
+        shared.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        shared.into_iter().map(|(tag, _)| tag.clone()).collect()
+    }
+}
+
+/// 추천 점수 계산 (공통 친구 + 카테고리 유사도 + 공통 태그)
+pub fn suggestion_score(mutual_friend_count: i64, category_similarity: f64, shared_tag_count: usize) -> f64 {
+    mutual_friend_count as f64 * MUTUAL_FRIEND_WEIGHT
+        + category_similarity * CATEGORY_WEIGHT
+        + shared_tag_count.min(MAX_SHARED_TAGS) as f64 * TAG_WEIGHT
+}
+
+/// 후보별 점수 계산 후 점수 순으로 정렬
+pub fn rank_candidates(
+    mutual_counts: &HashMap<String, i64>,
+    my_taste: &TasteProfile,
+    candidate_tastes: &HashMap<String, TasteProfile>,
+    limit: usize,
+) -> Vec<CachedSuggestion> {
+    let empty = TasteProfile::default();
+    let candidate_ids: HashSet<&String> = mutual_counts.keys().chain(candidate_tastes.keys()).collect();
+
+    let mut ranked: Vec<CachedSuggestion> = candidate_ids
+        .into_iter()
+        .map(|candidate_id| {
+            let mutual_friend_count = mutual_counts.get(candidate_id).copied().unwrap_or(0);
+            let taste = candidate_tastes.get(candidate_id).unwrap_or(&empty);
+            let mut shared_tags = my_taste.shared_tags(taste);
+            shared_tags.truncate(MAX_SHARED_TAGS);
+
+            CachedSuggestion {
+                user_id: candidate_id.clone(),
+                mutual_friend_count,
+                score: suggestion_score(
+                    mutual_friend_count,
+                    my_taste.category_similarity(taste),
+                    shared_tags.len(),
+                ),
+                shared_tags,
+            }
+        })
+        .filter(|suggestion| suggestion.score > 0.0)
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.user_id.cmp(&b.user_id)));
+    ranked.truncate(limit);
+    ranked
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(",")
+}
+
+/// 사용자들이 최근 좋아요한 포스트로 취향 프로필 생성
+async fn load_taste_profiles(
+    pool: &sqlx::SqlitePool,
+    user_ids: &[String],
+) -> Result<HashMap<String, TasteProfile>, sqlx::Error> {
+    let mut profiles: HashMap<String, TasteProfile> = HashMap::new();
+    if user_ids.is_empty() {
+        return Ok(profiles);
+    }
+
+    let query = format!(
+        "SELECT user_id, category, tags FROM (
+             SELECT pl.user_id, p.category, p.tags,
+                    ROW_NUMBER() OVER (PARTITION BY pl.user_id ORDER BY pl.created_at DESC) AS rn
+             FROM post_likes pl
+             INNER JOIN feed_posts p ON p.id = pl.post_id
+             WHERE pl.user_id IN ({})
+         )
+         WHERE rn <= ?",
+        placeholders(user_ids.len())
+    );
+
+    let mut query_builder = sqlx::query_as::<_, (String, String, String)>(&query);
+    for user_id in user_ids {
+        query_builder = query_builder.bind(user_id);
+    }
+
+    for (user_id, category, tags) in query_builder.bind(RECENT_LIKES_LIMIT).fetch_all(pool).await? {
+        profiles.entry(user_id).or_default().add_like(&category, &tags);
+    }
+
+    Ok(profiles)
+}
+
+/// 친구 추천 순위 조회 (FriendCache에 있으면 그대로 사용, 없으면 계산 후 저장)
+pub async fn load_friend_suggestions(
+    pool: &sqlx::SqlitePool,
+    cache: Option<&FriendCache>,
+    user_id: &str,
+) -> Result<Vec<CachedSuggestion>, sqlx::Error> {
+    if let Some(cache) = cache {
+        if let Ok(Some(ranked)) = cache.get_suggestions(user_id).await {
+            return Ok(ranked);
+        }
+    }
+
+    let friend_ids = Friendship::find_friend_ids_cached(pool, cache, user_id).await?;
+    let ranked = find_friend_suggestions(pool, user_id, &friend_ids).await?;
+
+    if let Some(cache) = cache {
+        if let Err(e) = cache.set_suggestions(user_id, &ranked).await {
+            log::warn!("Failed to cache friend suggestions for {}: {}", user_id, e);
+        }
+    }
+
+    Ok(ranked)
+}
+
+/// 친구 추천 계산
+///
+/// 후보: 친구의 친구 + 같은 카테고리 포스트를 좋아요한 사용자
+/// 제외: 본인, 이미 친구, 친구 요청 기록이 있는 사용자 (대기/거절/차단 등 상태와 무관)
+pub async fn find_friend_suggestions(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+    friend_ids: &[String],
+) -> Result<Vec<CachedSuggestion>, sqlx::Error> {
+    let mut excluded: HashSet<String> = sqlx::query_scalar::<_, String>(
+        "SELECT addressee_id FROM friend_requests WHERE requester_id = ?
+         UNION
+         SELECT requester_id FROM friend_requests WHERE addressee_id = ?"
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+    excluded.insert(user_id.to_string());
+    excluded.extend(friend_ids.iter().cloned());
+
+    // 공통 친구 수 (정규화된 friendships에서 친구의 친구 집계)
+    let mut mutual_counts: HashMap<String, i64> = HashMap::new();
+    if !friend_ids.is_empty() {
+        let friend_placeholders = placeholders(friend_ids.len());
+        let query = format!(
+            "SELECT CASE
+                 WHEN user_id IN ({0}) THEN friend_id
+                 ELSE user_id
+             END AS candidate_id, COUNT(*) AS mutual_count
+             FROM friendships
+             WHERE user_id IN ({0}) OR friend_id IN ({0})
+             GROUP BY candidate_id",
+            friend_placeholders
+        );
+
+        let mut query_builder = sqlx::query_as::<_, (String, i64)>(&query);
+        for _ in 0..3 {
+            for friend_id in friend_ids {
+                query_builder = query_builder.bind(friend_id);
+            }
+        }
+
+        for (candidate_id, count) in query_builder.fetch_all(pool).await? {
+            if !excluded.contains(&candidate_id) {
+                mutual_counts.insert(candidate_id, count);
+            }
+        }
+    }
+
+    let my_taste = load_taste_profiles(pool, &[user_id.to_string()])
+        .await?
+        .remove(user_id)
+        .unwrap_or_default();
+
+    // 취향 기반 후보 (내가 좋아요한 카테고리의 포스트를 많이 좋아요한 사용자)
+    let mut candidate_ids: HashSet<String> = mutual_counts.keys().cloned().collect();
+    if !my_taste.is_empty() {
+        let categories = my_taste.category_names();
+        let query = format!(
+            "SELECT pl.user_id FROM post_likes pl
+             INNER JOIN feed_posts p ON p.id = pl.post_id
+             WHERE p.category IN ({}) AND pl.user_id != ?
+             GROUP BY pl.user_id
+             ORDER BY COUNT(*) DESC
+             LIMIT ?",
+            placeholders(categories.len())
+        );
+
+        let mut query_builder = sqlx::query_scalar::<_, String>(&query);
+        for category in &categories {
+            query_builder = query_builder.bind(category);
+        }
+
+        let taste_candidates = query_builder
+            .bind(user_id)
+            .bind(TASTE_CANDIDATES_LIMIT)
+            .fetch_all(pool)
+            .await?;
+
+        candidate_ids.extend(taste_candidates.into_iter().filter(|id| !excluded.contains(id)));
+    }
+
+    let candidate_ids: Vec<String> = candidate_ids.into_iter().collect();
+    let candidate_tastes = load_taste_profiles(pool, &candidate_ids).await?;
+
+    Ok(rank_candidates(&mutual_counts, &my_taste, &candidate_tastes, MAX_SUGGESTIONS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(likes: &[(&str, &str)]) -> TasteProfile {
+        let mut profile = TasteProfile::default();
+        for (category, tags) in likes {
+            profile.add_like(category, tags);
+        }
+        profile
+    }
+
+    #[test]
+    fn test_category_similarity() {
+        let korean = profile(&[("KOREAN", "[]"), ("KOREAN", "[]")]);
+        let also_korean = profile(&[("KOREAN", "[]")]);
+        let cafe = profile(&[("CAFE", "[]")]);
+
+        assert!((korean.category_similarity(&also_korean) - 1.0).abs() < 1e-9);
+        assert_eq!(korean.category_similarity(&cafe), 0.0);
+        assert_eq!(korean.category_similarity(&TasteProfile::default()), 0.0);
+    }
+
+    #[test]
+    fn test_shared_tags_ordered_by_weight() {
+        let me = profile(&[("CAFE", r#"["브런치", "디저트"]"#), ("CAFE", r#"["브런치"]"#)]);
+        let other = profile(&[("CAFE", r#"["디저트", "브런치", "커피"]"#)]);
+
+        assert_eq!(me.shared_tags(&other), vec!["브런치", "디저트"]);
+    }
+
+    #[test]
+    fn test_rank_candidates_prefers_mutual_friends_then_taste() {
+        let me = profile(&[("JAPANESE", r#"["오마카세"]"#)]);
+
+        let mut mutual_counts = HashMap::new();
+        mutual_counts.insert("friend-of-friend".to_string(), 2);
+
+        let mut tastes = HashMap::new();
+        tastes.insert("same-taste".to_string(), profile(&[("JAPANESE", r#"["오마카세"]"#)]));
+        tastes.insert("different-taste".to_string(), profile(&[("CAFE", "[]")]));
+
+        let ranked = rank_candidates(&mutual_counts, &me, &tastes, 10);
+        let ids: Vec<&str> = ranked.iter().map(|s| s.user_id.as_str()).collect();
+
+        // 점수가 0인 후보(공통 친구도 취향 겹침도 없음)는 제외
+        assert_eq!(ids, vec!["friend-of-friend", "same-taste"]);
+        assert_eq!(ranked[1].shared_tags, vec!["오마카세"]);
+    }
+
+    #[test]
+    fn test_one_mutual_friend_outranks_any_taste_overlap() {
+        assert!(suggestion_score(1, 0.0, 0) > suggestion_score(0, 1.0, MAX_SHARED_TAGS + 3));
+        assert!(suggestion_score(1, 1.0, 1) > suggestion_score(1, 0.5, 0));
+    }
+
+    #[tokio::test]
+    #[ignore] // Redis 필요
+    async fn test_second_call_is_served_from_cache() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        for id in ["me", "friend", "friend-of-friend"] {
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, name, initial)
+                 VALUES (?, ? || '@example.com', 'x', ?, 'U')"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (user_id, friend_id) in [("friend", "me"), ("friend", "friend-of-friend")] {
+            sqlx::query("INSERT INTO friendships (user_id, friend_id) VALUES (?, ?)")
+                .bind(user_id)
+                .bind(friend_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let cache = FriendCache::new("redis://127.0.0.1:6379").unwrap();
+        cache.invalidate_user_cache("me").await.unwrap();
+
+        let first = load_friend_suggestions(&pool, Some(&cache), "me").await.unwrap();
+        assert_eq!(first[0].user_id, "friend-of-friend");
+
+        // DB가 바뀌어도 두 번째 호출은 캐시에서 반환
+        sqlx::query("DELETE FROM friendships").execute(&pool).await.unwrap();
+        let second = load_friend_suggestions(&pool, Some(&cache), "me").await.unwrap();
+        assert_eq!(second.len(), first.len());
+        assert_eq!(second[0].user_id, "friend-of-friend");
+
+        cache.invalidate_user_cache("me").await.unwrap();
+        assert!(load_friend_suggestions(&pool, Some(&cache), "me").await.unwrap().is_empty());
+    }
+}
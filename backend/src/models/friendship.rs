@@ -1,8 +1,11 @@
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::schema::loaders::FriendIdsLoader;
+
 /// Friendship 레코드 - user_id < friend_id 규칙으로 단방향 저장
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Friendship {
@@ -12,6 +15,9 @@ pub struct Friendship {
 }
 
 impl Friendship {
+    /// 검색 가중치에 쓰는 친구의 친구 최대 수
+    pub const MAX_FRIENDS_OF_FRIENDS: i64 = 1000;
+
     /// 두 사용자 ID를 정렬하여 (smaller, larger) 반환
     pub fn normalize_ids(id1: &str, id2: &str) -> (&str, &str) {
         if id1 < id2 {
@@ -30,6 +36,107 @@ impl Friendship {
             created_at: Utc::now(),
         }
     }
+
+    /// 두 친구 ID 목록의 교집합 (공통 친구), 입력 순서와 무관하게 정렬하여 반환
+    pub fn mutual_ids(friend_ids: &[String], other_friend_ids: &[String]) -> Vec<String> {
+        let other: std::collections::HashSet<&String> = other_friend_ids.iter().collect();
+        let mut mutual: Vec<String> = friend_ids
+            .iter()
+            .filter(|id| other.contains(id))
+            .cloned()
+            .collect();
+
+        mutual.sort();
+        mutual.dedup();
+        mutual
+    }
+
+    /// 친구 ID 목록 조회 (캐시 우선, 미스 시 DB 조회 후 캐시 저장)
+    ///
+    /// 같은 요청의 조회는 FriendIdsLoader로 묶어서 처리 (목록 필드의 N+1 방지)
+    pub async fn load_friend_ids(ctx: &Context<'_>, user_id: &str) -> Result<Vec<String>> {
+        if let Ok(loader) = ctx.data::<DataLoader<FriendIdsLoader>>() {
+            return Ok(loader.load_one(user_id.to_string()).await?.unwrap_or_default());
+        }
+
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let cache = ctx.data::<crate::cache::FriendCache>().ok();
+        Ok(Self::find_friend_ids_cached(pool, cache, user_id).await?)
+    }
+
+    /// 친구 ID 목록 조회 (캐시가 있으면 캐시 우선, 미스 시 DB 조회 후 캐시 저장)
+    pub async fn find_friend_ids_cached(
+        pool: &sqlx::SqlitePool,
+        cache: Option<&crate::cache::FriendCache>,
+        user_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let Some(cache) = cache else {
+            // 캐시 사용 불가: DB에서 직접 조회
+            return Self::find_friend_ids(pool, user_id).await;
+        };
+
+        if let Ok(Some(ids)) = cache.get_friend_ids(user_id).await {
+            return Ok(ids);
+        }
+
+        // 캐시 미스: DB에서 조회 후 캐시에 저장
+        let ids = Self::find_friend_ids(pool, user_id).await?;
+        let _ = cache.set_friend_ids(user_id, &ids).await;
+        Ok(ids)
+    }
+
+    /// 사용자의 친구 ID 목록 조회 (user_id가 양쪽 컬럼 어디에나 있을 수 있음)
+    pub async fn find_friend_ids(
+        pool: &sqlx::SqlitePool,
+        user_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT CASE
+                WHEN user_id = ? THEN friend_id
+                ELSE user_id
+            END AS friend_user_id
+            FROM friendships
+            WHERE user_id = ? OR friend_id = ?"
+        )
+        .bind(user_id)
+        .bind(user_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// 친구의 친구 ID 목록 (본인과 이미 친구인 사용자 제외, 최대 limit명)
+    pub async fn find_friends_of_friends(
+        pool: &sqlx::SqlitePool,
+        user_id: &str,
+        friend_ids: &[String],
+        limit: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        if friend_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let placeholders = vec!["?"; friend_ids.len()].join(",");
+        let query = format!(
+            "SELECT DISTINCT candidate_id FROM (
+                SELECT friend_id AS candidate_id FROM friendships WHERE user_id IN ({placeholders})
+                UNION ALL
+                SELECT user_id AS candidate_id FROM friendships WHERE friend_id IN ({placeholders})
+            )
+            WHERE candidate_id != ? AND candidate_id NOT IN ({placeholders})
+            LIMIT ?"
+        );
+
+        let mut query_builder = sqlx::query_scalar::<_, String>(&query);
+        for friend_id in friend_ids.iter().chain(friend_ids) {
+            query_builder = query_builder.bind(friend_id);
+        }
+        query_builder = query_builder.bind(user_id);
+        for friend_id in friend_ids {
+            query_builder = query_builder.bind(friend_id);
+        }
+        query_builder.bind(limit).fetch_all(pool).await
+    }
 }
 
 #[Object]
@@ -56,3 +163,23 @@ impl Friendship {
         self.created_at.to_rfc3339()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ids() {
+        assert_eq!(Friendship::normalize_ids("b", "a"), ("a", "b"));
+        assert_eq!(Friendship::normalize_ids("a", "b"), ("a", "b"));
+    }
+
+    #[test]
+    fn test_mutual_ids() {
+        let mine = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+        let theirs = vec!["b".to_string(), "d".to_string(), "c".to_string()];
+
+        assert_eq!(Friendship::mutual_ids(&mine, &theirs), vec!["b", "c"]);
+        assert!(Friendship::mutual_ids(&mine, &[]).is_empty());
+    }
+}
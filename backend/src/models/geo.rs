@@ -0,0 +1,113 @@
+/// 지구 평균 반지름 (km)
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// 위도 1도당 거리 (km)
+const KM_PER_LATITUDE_DEGREE: f64 = 111.32;
+
+/// 주변 검색 최대 반경 (km)
+pub const MAX_NEARBY_RADIUS_KM: f64 = 50.0;
+
+/// 좌표 검증 (위도 -90~90, 경도 -180~180)
+pub fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), String> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err("Latitude must be between -90 and 90".to_string());
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err("Longitude must be between -180 and 180".to_string());
+    }
+    Ok(())
+}
+
+/// 입력 좌표 쌍 검증 (둘 다 있거나 둘 다 없어야 함)
+pub fn validate_optional_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<Option<(f64, f64)>, String> {
+    match (latitude, longitude) {
+        (Some(lat), Some(lng)) => {
+            validate_coordinates(lat, lng)?;
+            Ok(Some((lat, lng)))
+        }
+        (None, None) => Ok(None),
+        _ => Err("Latitude and longitude must be provided together".to_string()),
+    }
+}
+
+/// 두 좌표 사이의 대원 거리 (haversine, km)
+pub fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lng = (lng2 - lng1).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// 반경 검색용 바운딩 박스 (SQLite 인덱스 조회 후 haversine으로 정확히 거름)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lng: f64,
+    pub max_lng: f64,
+}
+
+impl BoundingBox {
+    pub fn around(latitude: f64, longitude: f64, radius_km: f64) -> Self {
+        let lat_delta = radius_km / KM_PER_LATITUDE_DEGREE;
+        let min_lat = (latitude - lat_delta).max(-90.0);
+        let max_lat = (latitude + lat_delta).min(90.0);
+
+        // 극지방이나 날짜변경선에 걸치면 경도 범위 전체를 사용
+        let cos_lat = latitude.to_radians().cos();
+        if max_lat >= 90.0 || min_lat <= -90.0 || cos_lat <= f64::EPSILON {
+            return Self { min_lat, max_lat, min_lng: -180.0, max_lng: 180.0 };
+        }
+
+        let lng_delta = radius_km / (KM_PER_LATITUDE_DEGREE * cos_lat);
+        let (min_lng, max_lng) = (longitude - lng_delta, longitude + lng_delta);
+        if min_lng < -180.0 || max_lng > 180.0 {
+            return Self { min_lat, max_lat, min_lng: -180.0, max_lng: 180.0 };
+        }
+
+        Self { min_lat, max_lat, min_lng, max_lng }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_haversine_km() {
+        // 서울시청 - 강남역 약 8.9km
+        let distance = haversine_km(37.5663, 126.9779, 37.4979, 127.0276);
+        assert!((distance - 8.86).abs() < 0.1, "distance = {}", distance);
+        assert_eq!(haversine_km(37.5, 127.0, 37.5, 127.0), 0.0);
+    }
+
+    #[test]
+    fn test_bounding_box_contains_radius() {
+        let (lat, lng) = (37.5663, 126.9779);
+        let bbox = BoundingBox::around(lat, lng, 1.0);
+
+        // 박스 모서리까지의 거리는 반경 이상이어야 함
+        assert!(haversine_km(lat, lng, bbox.max_lat, lng) >= 0.99);
+        assert!(haversine_km(lat, lng, lat, bbox.max_lng) >= 0.99);
+        assert!(bbox.min_lat < lat && lat < bbox.max_lat);
+        assert!(bbox.min_lng < lng && lng < bbox.max_lng);
+
+        let wrapped = BoundingBox::around(0.0, 179.99, 5.0);
+        assert_eq!((wrapped.min_lng, wrapped.max_lng), (-180.0, 180.0));
+    }
+
+    #[test]
+    fn test_validate_optional_coordinates() {
+        assert_eq!(validate_optional_coordinates(None, None), Ok(None));
+        assert_eq!(validate_optional_coordinates(Some(37.5), Some(127.0)), Ok(Some((37.5, 127.0))));
+        assert!(validate_optional_coordinates(Some(37.5), None).is_err());
+        assert!(validate_optional_coordinates(Some(91.0), Some(127.0)).is_err());
+        assert!(validate_optional_coordinates(Some(37.5), Some(181.0)).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use async_graphql::*;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+
+use crate::models::post::ImageUrls;
+use crate::storage::metadata::{format_taken_at, PhotoMetadata};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
+#[sqlx(type_name = "TEXT")]
+pub enum ImageUploadStatus {
+    /// 업로드 URL 발급, 원본 업로드/확정 대기
+    #[sqlx(rename = "PENDING")]
+    Pending,
+    /// 변형 생성 완료 (포스트에서 참조 가능)
+    #[sqlx(rename = "READY")]
+    Ready,
+}
+
+/// presigned URL로 저장소에 직접 올린 이미지
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImageUpload {
+    pub id: String,
+    pub user_id: String,
+    /// 원본 객체 키 (확정 후 삭제)
+    pub original_key: String,
+    pub content_type: String,
+    pub status: ImageUploadStatus,
+    /// 처리된 변형 URL (JSON 문자열)
+    pub image_urls: Option<String>,
+    pub post_id: Option<String>,
+    /// 업로드 URL 만료 시각
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// EXIF 촬영 시각/좌표 (확정 시 사용자가 동의한 경우에만 기록)
+    pub taken_at: Option<NaiveDateTime>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl ImageUpload {
+    /// 원본 객체 키: uploads/{user_id}/{upload_id}/original
+    pub fn original_key_for(user_id: &str, upload_id: &str) -> String {
+        format!("uploads/{}/{}/original", user_id, upload_id)
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        id: &str,
+        user_id: &str,
+        content_type: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query_as::<_, ImageUpload>(
+            "INSERT INTO image_uploads (id, user_id, original_key, content_type, status, expires_at, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'PENDING', ?, ?, ?)
+             RETURNING *"
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(Self::original_key_for(user_id, id))
+        .bind(content_type)
+        .bind(expires_at)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// 본인의 업로드 조회
+    pub async fn find_for_user(pool: &SqlitePool, id: &str, user_id: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ImageUpload>("SELECT * FROM image_uploads WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// 변형 생성 완료 기록
+    pub async fn mark_ready(
+        pool: &SqlitePool,
+        id: &str,
+        image_urls: &ImageUrls,
+        metadata: &PhotoMetadata,
+    ) -> Result<Self, sqlx::Error> {
+        let image_urls_json = serde_json::to_string(image_urls).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+        sqlx::query_as::<_, ImageUpload>(
+            "UPDATE image_uploads
+             SET status = 'READY', image_urls = ?, taken_at = ?, latitude = ?, longitude = ?, updated_at = ?
+             WHERE id = ?
+             RETURNING *"
+        )
+        .bind(image_urls_json)
+        .bind(metadata.taken_at)
+        .bind(metadata.coordinates.map(|(lat, _)| lat))
+        .bind(metadata.coordinates.map(|(_, lng)| lng))
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// 포스트에 연결 (포스트 생성과 같은 트랜잭션에서 호출)
+    ///
+    /// 본인의 READY 상태이고 아직 연결되지 않은 업로드만 연결하며, 변형 URL(JSON) 반환
+    pub async fn attach_to_post(
+        conn: &mut SqliteConnection,
+        id: &str,
+        user_id: &str,
+        post_id: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "UPDATE image_uploads SET post_id = ?, updated_at = ?
+             WHERE id = ? AND user_id = ? AND status = 'READY' AND post_id IS NULL
+             RETURNING image_urls"
+        )
+        .bind(post_id)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await
+        .map(Option::flatten)
+    }
+
+    /// 만료 후 `before`까지 확정되지 않은 업로드 삭제 (최대 limit개), 삭제한 업로드 반환
+    pub async fn delete_expired_pending(
+        pool: &SqlitePool,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ImageUpload>(
+            "DELETE FROM image_uploads
+             WHERE id IN (
+                 SELECT id FROM image_uploads
+                 WHERE status = 'PENDING' AND expires_at < ?
+                 LIMIT ?
+             )
+             RETURNING *"
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// `before` 이전에 확정된 뒤 포스트에 연결되지 않은 업로드 삭제 (최대 limit개), 삭제한 업로드 반환
+    pub async fn delete_unattached_ready(
+        pool: &SqlitePool,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ImageUpload>(
+            "DELETE FROM image_uploads
+             WHERE id IN (
+                 SELECT id FROM image_uploads
+                 WHERE status = 'READY' AND post_id IS NULL AND updated_at < ?
+                 LIMIT ?
+             )
+             RETURNING *"
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// 포스트에 연결된 업로드 ID (직접 업로드가 아니면 None)
+    pub async fn find_id_for_post(pool: &SqlitePool, post_id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT id FROM image_uploads WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_optional(pool)
+            .await
+    }
+}
+
+#[Object]
+impl ImageUpload {
+    /// createFeedPost의 imageId로 사용
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn status(&self) -> ImageUploadStatus {
+        self.status
+    }
+
+    async fn image_urls(&self) -> Option<ImageUrls> {
+        self.image_urls
+            .as_deref()
+            .and_then(|json_str| serde_json::from_str(json_str).ok())
+    }
+
+    /// 추천 촬영 시각 (YYYY-MM-DDTHH:MM:SS, createFeedPost의 takenAt에 사용)
+    async fn taken_at(&self) -> Option<String> {
+        self.taken_at.as_ref().map(format_taken_at)
+    }
+
+    /// 추천 위치 (사진 촬영 좌표)
+    async fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    async fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.to_rfc3339()
+    }
+}
+
+/// 직접 업로드 안내 (PUT 요청에 Content-Type 헤더를 그대로 보내야 함)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ImageUploadTicket {
+    /// finalizeImageUpload에 전달할 업로드 ID
+    pub upload_id: String,
+    pub upload_url: String,
+    pub method: String,
+    pub content_type: String,
+    pub expires_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_original_key_for() {
+        assert_eq!(ImageUpload::original_key_for("u1", "abc"), "uploads/u1/abc/original");
+    }
+}
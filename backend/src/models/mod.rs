@@ -3,13 +3,22 @@ pub mod post;
 pub mod comment;
 pub mod friendship;
 pub mod friend_request;
+pub mod friend_suggestion;
 pub mod oauth_provider;
 pub mod audit_log;
+pub mod notification;
+pub mod geo;
+pub mod restaurant;
+pub mod image_upload;
 
 pub use user::{User, UserStatus};
-pub use post::{FeedPost, Category};
+pub use post::{FeedPost, Category, ImageStatus, NearbyPost, PostImageStatus, PostLikeCount, UpdateFeedPostInput};
 pub use comment::Comment;
 pub use friendship::Friendship;
-pub use friend_request::{FriendRequest, FriendRequestStatus, FriendStats};
+pub use friend_suggestion::FriendSuggestion;
+pub use friend_request::{FriendRequest, FriendRequestStatus, FriendStats, BLOCKED_USER_IDS_SQL};
 pub use oauth_provider::{OAuthProvider, CreateOAuthProvider};
 pub use audit_log::{AuditLog, CreateAuditLog, log_success, log_failure};
+pub use restaurant::Restaurant;
+pub use notification::{CreateNotification, Notification, NotificationKind};
+pub use image_upload::{ImageUpload, ImageUploadStatus, ImageUploadTicket};
@@ -0,0 +1,290 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::schema::loaders::UserLoader;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
+#[sqlx(type_name = "TEXT")]
+pub enum NotificationKind {
+    /// 내 포스트에 좋아요
+    #[sqlx(rename = "POST_LIKED")]
+    PostLiked,
+    /// 댓글에서 나를 멘션
+    #[sqlx(rename = "COMMENT_MENTION")]
+    CommentMention,
+    /// 친구 요청 도착
+    #[sqlx(rename = "FRIEND_REQUEST_RECEIVED")]
+    FriendRequestReceived,
+    /// 내가 보낸 친구 요청이 수락됨
+    #[sqlx(rename = "FRIEND_REQUEST_ACCEPTED")]
+    FriendRequestAccepted,
+}
+
+/// 수신자별 알림
+///
+/// 대상(target)은 kind에 따라 post_id / comment_id / friend_request_id 중 하나 이상
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub id: String,
+    pub recipient_id: String,
+    pub actor_id: String,
+    pub kind: NotificationKind,
+    pub post_id: Option<String>,
+    pub comment_id: Option<String>,
+    pub friend_request_id: Option<String>,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+/// 알림 생성 입력
+#[derive(Debug, Clone)]
+pub struct CreateNotification {
+    pub recipient_id: String,
+    pub actor_id: String,
+    pub kind: NotificationKind,
+    pub post_id: Option<String>,
+    pub comment_id: Option<String>,
+    pub friend_request_id: Option<String>,
+}
+
+impl CreateNotification {
+    pub fn post_liked(recipient_id: &str, actor_id: &str, post_id: &str) -> Self {
+        Self {
+            recipient_id: recipient_id.to_string(),
+            actor_id: actor_id.to_string(),
+            kind: NotificationKind::PostLiked,
+            post_id: Some(post_id.to_string()),
+            comment_id: None,
+            friend_request_id: None,
+        }
+    }
+
+    pub fn comment_mention(recipient_id: &str, actor_id: &str, post_id: &str, comment_id: &str) -> Self {
+        Self {
+            recipient_id: recipient_id.to_string(),
+            actor_id: actor_id.to_string(),
+            kind: NotificationKind::CommentMention,
+            post_id: Some(post_id.to_string()),
+            comment_id: Some(comment_id.to_string()),
+            friend_request_id: None,
+        }
+    }
+
+    pub fn friend_request(
+        kind: NotificationKind,
+        recipient_id: &str,
+        actor_id: &str,
+        friend_request_id: &str,
+    ) -> Self {
+        Self {
+            recipient_id: recipient_id.to_string(),
+            actor_id: actor_id.to_string(),
+            kind,
+            post_id: None,
+            comment_id: None,
+            friend_request_id: Some(friend_request_id.to_string()),
+        }
+    }
+}
+
+impl Notification {
+    /// 알림 생성 (자기 자신의 행동에는 알림을 만들지 않음)
+    pub async fn create(
+        pool: &sqlx::SqlitePool,
+        input: CreateNotification,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        if input.recipient_id == input.actor_id {
+            return Ok(None);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO notifications
+             (id, recipient_id, actor_id, kind, post_id, comment_id, friend_request_id, is_read, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, 0, ?)"
+        )
+        .bind(&id)
+        .bind(&input.recipient_id)
+        .bind(&input.actor_id)
+        .bind(input.kind)
+        .bind(&input.post_id)
+        .bind(&input.comment_id)
+        .bind(&input.friend_request_id)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(Some(Self {
+            id,
+            recipient_id: input.recipient_id,
+            actor_id: input.actor_id,
+            kind: input.kind,
+            post_id: input.post_id,
+            comment_id: input.comment_id,
+            friend_request_id: input.friend_request_id,
+            is_read: false,
+            created_at: now,
+            read_at: None,
+        }))
+    }
+
+    /// 좋아요 취소 시 아직 남아있는 좋아요 알림 삭제
+    pub async fn delete_post_like(
+        pool: &sqlx::SqlitePool,
+        actor_id: &str,
+        post_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM notifications WHERE kind = 'POST_LIKED' AND actor_id = ? AND post_id = ?"
+        )
+        .bind(actor_id)
+        .bind(post_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 댓글 삭제 시 해당 댓글의 멘션 알림 삭제 (댓글 삭제와 같은 트랜잭션에서 호출)
+    pub async fn delete_comment_mentions(
+        conn: &mut sqlx::SqliteConnection,
+        comment_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM notifications WHERE kind = 'COMMENT_MENTION' AND comment_id = ?"
+        )
+        .bind(comment_id)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 읽지 않은 알림 수
+    pub async fn unread_count(pool: &sqlx::SqlitePool, user_id: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM notifications WHERE recipient_id = ? AND is_read = 0"
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// 알림 읽음 처리 (ids가 없으면 전체), 변경된 알림 수 반환
+    pub async fn mark_read(
+        pool: &sqlx::SqlitePool,
+        user_id: &str,
+        ids: Option<&[String]>,
+    ) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+
+        let result = match ids {
+            Some([]) => return Ok(0),
+            Some(ids) => {
+                let placeholders = vec!["?"; ids.len()].join(",");
+                let query = format!(
+                    "UPDATE notifications SET is_read = 1, read_at = ?
+                     WHERE recipient_id = ? AND is_read = 0 AND id IN ({})",
+                    placeholders
+                );
+
+                let mut query_builder = sqlx::query(&query).bind(now).bind(user_id);
+                for id in ids {
+                    query_builder = query_builder.bind(id);
+                }
+                query_builder.execute(pool).await?
+            }
+            None => {
+                sqlx::query(
+                    "UPDATE notifications SET is_read = 1, read_at = ?
+                     WHERE recipient_id = ? AND is_read = 0"
+                )
+                .bind(now)
+                .bind(user_id)
+                .execute(pool)
+                .await?
+            }
+        };
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[Object]
+impl Notification {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn kind(&self) -> NotificationKind {
+        self.kind
+    }
+
+    /// 알림을 발생시킨 사용자
+    async fn actor(&self, ctx: &Context<'_>) -> Result<crate::models::User> {
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let user = loader.load_one(self.actor_id.clone()).await?;
+        user.ok_or_else(|| "User not found".into())
+    }
+
+    /// 대상 포스트 (좋아요, 멘션)
+    async fn post(&self, ctx: &Context<'_>) -> Result<Option<crate::models::FeedPost>> {
+        let Some(post_id) = &self.post_id else {
+            return Ok(None);
+        };
+
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let post = sqlx::query_as::<_, crate::models::FeedPost>("SELECT * FROM feed_posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(post)
+    }
+
+    /// 대상 댓글 (멘션)
+    async fn comment(&self, ctx: &Context<'_>) -> Result<Option<crate::models::Comment>> {
+        let Some(comment_id) = &self.comment_id else {
+            return Ok(None);
+        };
+
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let comment = sqlx::query_as::<_, crate::models::Comment>("SELECT * FROM comments WHERE id = ?")
+            .bind(comment_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(comment)
+    }
+
+    /// 대상 친구 요청 (친구 요청 도착/수락)
+    async fn friend_request(&self, ctx: &Context<'_>) -> Result<Option<crate::models::FriendRequest>> {
+        let Some(friend_request_id) = &self.friend_request_id else {
+            return Ok(None);
+        };
+
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let request = sqlx::query_as::<_, crate::models::FriendRequest>("SELECT * FROM friend_requests WHERE id = ?")
+            .bind(friend_request_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(request)
+    }
+
+    async fn is_read(&self) -> bool {
+        self.is_read
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.to_rfc3339()
+    }
+
+    async fn read_at(&self) -> Option<String> {
+        self.read_at.map(|t| t.to_rfc3339())
+    }
+}
@@ -1,8 +1,11 @@
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::schema::loaders::{LikeKey, LikedByViewerLoader, UserLoader};
+
 /// 이미지 포맷별 URL
 ///
 /// 엔터프라이즈 전략: 브라우저가 최적 포맷 선택 (Picture 엘리먼트)
@@ -44,6 +47,26 @@ pub enum Category {
     Dessert,
 }
 
+/// 포스트 이미지 처리 상태
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Enum, sqlx::Type)]
+#[sqlx(rename_all = "UPPERCASE")]
+pub enum ImageStatus {
+    /// 원본 업로드 완료, 변형 생성 대기/진행 중
+    Processing,
+    /// 변형 생성 완료 (imageUrls 사용 가능)
+    Ready,
+    /// 재시도 한도 초과
+    Failed,
+}
+
+/// 포스트 이미지 처리 상태 변경 알림 (postImageStatusChanged 구독)
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PostImageStatus {
+    pub post_id: String,
+    pub status: ImageStatus,
+    pub image_urls: Option<ImageUrls>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct FeedPost {
     pub id: String,
@@ -55,12 +78,21 @@ pub struct FeedPost {
     pub food_image: Option<String>,
     /// 다중 포맷/해상도 이미지 URL (JSON 문자열)
     pub image_urls: Option<String>,
+    /// 이미지 처리 상태 (이미지가 없으면 None)
+    pub image_status: Option<ImageStatus>,
     pub category: Category,
     pub tags: String, // JSON string
     pub likes: i64,
     pub comments_count: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// 위치 좌표 (선택)
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// 리뷰 대상 식당 (선택)
+    pub restaurant_id: Option<String>,
+    /// 사진 촬영 시각 (촬영 기기의 현지 시각, 선택)
+    pub taken_at: Option<NaiveDateTime>,
 }
 
 #[Object]
@@ -70,14 +102,9 @@ impl FeedPost {
     }
 
     async fn author(&self, ctx: &Context<'_>) -> Result<crate::models::User> {
-        let pool = ctx.data::<sqlx::SqlitePool>()?;
-        let user = sqlx::query_as::<_, crate::models::User>(
-            "SELECT * FROM users WHERE id = ?"
-        )
-        .bind(&self.author_id)
-        .fetch_one(pool)
-        .await?;
-        Ok(user)
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let user = loader.load_one(self.author_id.clone()).await?;
+        user.ok_or_else(|| "Author not found".into())
     }
 
     async fn title(&self) -> &str {
@@ -92,12 +119,40 @@ impl FeedPost {
         &self.location
     }
 
+    /// 리뷰 대상 식당
+    async fn restaurant(&self, ctx: &Context<'_>) -> Result<Option<crate::models::Restaurant>> {
+        let Some(restaurant_id) = &self.restaurant_id else {
+            return Ok(None);
+        };
+
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        Ok(crate::models::Restaurant::find_by_id(pool, restaurant_id).await?)
+    }
+
+    async fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    async fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+
     async fn rating(&self) -> f64 {
         self.rating
     }
 
-    async fn food_image(&self) -> Option<&str> {
-        self.food_image.as_deref()
+    /// 사진 촬영 시각 (YYYY-MM-DDTHH:MM:SS)
+    async fn taken_at(&self) -> Option<String> {
+        self.taken_at.as_ref().map(crate::storage::metadata::format_taken_at)
+    }
+
+    /// 대표 이미지 URL (중간 크기 JPEG, 이미지 처리 이전 포스트는 저장된 값)
+    async fn food_image(&self) -> Option<String> {
+        self.image_urls
+            .as_deref()
+            .and_then(|json_str| serde_json::from_str::<ImageUrls>(json_str).ok())
+            .map(|urls| urls.medium.jpeg)
+            .or_else(|| self.food_image.clone())
     }
 
     /// 반응형 이미지 URL (다중 포맷/해상도)
@@ -111,6 +166,11 @@ impl FeedPost {
         }
     }
 
+    /// 이미지 처리 상태 (PROCESSING이면 imageUrls가 아직 없음)
+    async fn image_status(&self) -> Option<ImageStatus> {
+        self.image_status
+    }
+
     async fn category(&self) -> Category {
         self.category
     }
@@ -140,28 +200,66 @@ impl FeedPost {
         let user_id = ctx.data_opt::<String>();
 
         if let Some(user_id) = user_id {
-            let pool = ctx.data::<sqlx::SqlitePool>()?;
-            let result = sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM post_likes WHERE post_id = ? AND user_id = ?"
-            )
-            .bind(&self.id)
-            .bind(user_id)
-            .fetch_one(pool)
-            .await?;
-            Ok(result > 0)
+            let loader = ctx.data::<DataLoader<LikedByViewerLoader>>()?;
+            let liked = loader
+                .load_one(LikeKey {
+                    user_id: user_id.clone(),
+                    post_id: self.id.clone(),
+                })
+                .await?;
+            Ok(liked.unwrap_or(false))
         } else {
             Ok(false)
         }
     }
 }
 
+/// 포스트 좋아요 수 변경 알림 (postLikeCountChanged 구독)
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PostLikeCount {
+    pub post_id: String,
+    pub likes: i64,
+}
+
+/// 주변 포스트 검색 결과 (거리순)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NearbyPost {
+    pub post: FeedPost,
+    /// 검색 위치로부터의 거리 (km)
+    pub distance_km: f64,
+}
+
 #[derive(Debug, InputObject)]
 pub struct CreateFeedPostInput {
     pub title: String,
     pub content: String,
     pub location: String,
+    /// 위치 좌표 (위도/경도는 함께 입력)
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// 리뷰 대상 식당 (matchRestaurant로 조회/생성한 ID)
+    pub restaurant_id: Option<String>,
     pub rating: f64,
     pub category: Category,
     pub tags: Vec<String>,
+    /// 음식 사진 (GraphQL multipart 업로드, JPEG/PNG/WebP)
+    pub image: Option<Upload>,
+    /// 직접 업로드 후 finalizeImageUpload로 처리한 이미지 ID
+    pub image_id: Option<String>,
+    /// 사진 촬영 시각 (YYYY-MM-DDTHH:MM:SS, finalizeImageUpload의 추천 값 사용 가능)
+    pub taken_at: Option<String>,
+    /// Base64 데이터 URI (이전 방식, 처리 후 원본은 저장하지 않음)
+    #[graphql(deprecation = "Use `image` (multipart upload) instead")]
     pub food_image: Option<String>,
 }
+
+/// 피드 포스트 수정 입력 (지정한 필드만 변경)
+#[derive(Debug, InputObject)]
+pub struct UpdateFeedPostInput {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub location: Option<String>,
+    pub rating: Option<f64>,
+    pub category: Option<Category>,
+    pub tags: Option<Vec<String>>,
+}
@@ -0,0 +1,396 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::geo;
+use crate::models::{Category, FeedPost, BLOCKED_USER_IDS_SQL};
+
+/// 같은 식당으로 볼 최대 거리 (km)
+const MATCH_RADIUS_KM: f64 = 0.3;
+
+/// 주소로 취급할 토큰 접미사 (시/도/구/군/동/읍/면, 도로명)
+const ADDRESS_SUFFIXES: &[&str] = &["시", "도", "구", "군", "동", "읍", "면", "로", "길"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Restaurant {
+    pub id: String,
+    pub name: String,
+    pub normalized_name: String,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub categories: String, // JSON array of Category
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 별점별 리뷰 수
+#[derive(Debug, Clone, PartialEq, Eq, SimpleObject)]
+pub struct RatingCount {
+    /// 별점 (1~5, 반올림)
+    pub rating: i32,
+    pub count: i64,
+}
+
+/// 이름 정규화 (소문자, 공백/특수문자 제거)
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 자유 입력 위치를 (식당 이름, 주소)로 분리
+///
+/// "강남구 청담동 라트라토리아" → ("라트라토리아", Some("강남구 청담동"))
+pub fn parse_location(location: &str) -> (String, Option<String>) {
+    let tokens: Vec<&str> = location.split_whitespace().collect();
+    let is_address = |token: &str| {
+        token.starts_with(|c: char| c.is_ascii_digit())
+            || (token.chars().count() > 1 && ADDRESS_SUFFIXES.iter().any(|s| token.ends_with(s)))
+    };
+
+    let address_len = tokens.iter().take_while(|t| is_address(t)).count();
+    if address_len == 0 || address_len == tokens.len() {
+        return (tokens.join(" "), None);
+    }
+
+    (
+        tokens[address_len..].join(" "),
+        Some(tokens[..address_len].join(" ")),
+    )
+}
+
+/// 별점 분포 (1~5 모든 구간 포함, 없는 구간은 0)
+pub fn rating_distribution(counts: &[(i32, i64)]) -> Vec<RatingCount> {
+    (1..=5)
+        .map(|rating| RatingCount {
+            rating,
+            count: counts
+                .iter()
+                .filter(|(r, _)| *r == rating)
+                .map(|(_, c)| c)
+                .sum(),
+        })
+        .collect()
+}
+
+impl Restaurant {
+    pub fn category_list(&self) -> Vec<Category> {
+        serde_json::from_str(&self.categories).unwrap_or_default()
+    }
+
+    /// 같은 장소인지 판단 (좌표 → 주소 순으로 비교, 정보가 부족하면 같은 곳으로 간주)
+    fn is_same_place(&self, address: Option<&str>, coordinates: Option<(f64, f64)>) -> bool {
+        if let (Some(lat), Some(lng), Some((other_lat, other_lng))) =
+            (self.latitude, self.longitude, coordinates)
+        {
+            return geo::haversine_km(lat, lng, other_lat, other_lng) <= MATCH_RADIUS_KM;
+        }
+
+        match (self.address.as_deref(), address) {
+            (Some(mine), Some(other)) => {
+                let (mine, other) = (normalize_name(mine), normalize_name(other));
+                mine.contains(&other) || other.contains(&mine)
+            }
+            _ => true,
+        }
+    }
+
+    /// 이름이 같은 후보 중 같은 장소로 판단되는 가장 가까운 식당
+    pub fn best_match<'a>(
+        candidates: &'a [Restaurant],
+        address: Option<&str>,
+        coordinates: Option<(f64, f64)>,
+    ) -> Option<&'a Restaurant> {
+        let distance = |r: &Restaurant| match (r.latitude, r.longitude, coordinates) {
+            (Some(lat), Some(lng), Some((other_lat, other_lng))) => {
+                geo::haversine_km(lat, lng, other_lat, other_lng)
+            }
+            _ => f64::MAX,
+        };
+
+        candidates
+            .iter()
+            .filter(|r| r.is_same_place(address, coordinates))
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    pub async fn find_by_id(pool: &sqlx::SqlitePool, id: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Restaurant>("SELECT * FROM restaurants WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// 자유 입력 위치로 식당을 찾고, 없으면 생성
+    ///
+    /// 기존 식당이면 비어있는 주소/좌표를 채우고 카테고리를 추가
+    pub async fn match_or_create(
+        pool: &sqlx::SqlitePool,
+        location: &str,
+        coordinates: Option<(f64, f64)>,
+        category: Option<Category>,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let (name, address) = parse_location(location);
+        let normalized_name = normalize_name(&name);
+        if normalized_name.is_empty() {
+            return Err("Location must contain a restaurant name".into());
+        }
+
+        let candidates = sqlx::query_as::<_, Restaurant>(
+            "SELECT * FROM restaurants WHERE normalized_name = ? ORDER BY created_at ASC"
+        )
+        .bind(&normalized_name)
+        .fetch_all(pool)
+        .await?;
+
+        let now = Utc::now();
+
+        if let Some(existing) = Self::best_match(&candidates, address.as_deref(), coordinates) {
+            let mut categories = existing.category_list();
+            if let Some(category) = category {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+
+            sqlx::query(
+                "UPDATE restaurants
+                 SET address = COALESCE(address, ?), latitude = COALESCE(latitude, ?),
+                     longitude = COALESCE(longitude, ?), categories = ?, updated_at = ?
+                 WHERE id = ?"
+            )
+            .bind(&address)
+            .bind(coordinates.map(|(lat, _)| lat))
+            .bind(coordinates.map(|(_, lng)| lng))
+            .bind(serde_json::to_string(&categories)?)
+            .bind(now)
+            .bind(&existing.id)
+            .execute(pool)
+            .await?;
+
+            let restaurant = Self::find_by_id(pool, &existing.id)
+                .await?
+                .ok_or("Restaurant not found")?;
+            return Ok(restaurant);
+        }
+
+        let restaurant = Restaurant {
+            id: Uuid::new_v4().to_string(),
+            name,
+            normalized_name,
+            address,
+            latitude: coordinates.map(|(lat, _)| lat),
+            longitude: coordinates.map(|(_, lng)| lng),
+            categories: serde_json::to_string(&category.into_iter().collect::<Vec<_>>())?,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query(
+            "INSERT INTO restaurants
+             (id, name, normalized_name, address, latitude, longitude, categories, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&restaurant.id)
+        .bind(&restaurant.name)
+        .bind(&restaurant.normalized_name)
+        .bind(&restaurant.address)
+        .bind(restaurant.latitude)
+        .bind(restaurant.longitude)
+        .bind(&restaurant.categories)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(restaurant)
+    }
+
+    /// 포스트 작성 시 카테고리 추가 (이미 있으면 무시)
+    pub async fn add_category(
+        &self,
+        pool: &sqlx::SqlitePool,
+        category: Category,
+    ) -> Result<(), sqlx::Error> {
+        let mut categories = self.category_list();
+        if categories.contains(&category) {
+            return Ok(());
+        }
+        categories.push(category);
+
+        sqlx::query("UPDATE restaurants SET categories = ?, updated_at = ? WHERE id = ?")
+            .bind(serde_json::to_string(&categories).unwrap_or_else(|_| "[]".to_string()))
+            .bind(Utc::now())
+            .bind(&self.id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[Object]
+impl Restaurant {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    async fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    async fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+
+    async fn categories(&self) -> Vec<Category> {
+        self.category_list()
+    }
+
+    /// 평균 평점 (리뷰가 없으면 null)
+    async fn average_rating(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let average: Option<f64> = sqlx::query_scalar(
+            "SELECT AVG(rating) FROM feed_posts WHERE restaurant_id = ?"
+        )
+        .bind(&self.id)
+        .fetch_one(pool)
+        .await?;
+
+        // 소수점 둘째 자리까지
+        Ok(average.map(|avg| (avg * 100.0).round() / 100.0))
+    }
+
+    /// 리뷰(포스트) 수
+    async fn review_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM feed_posts WHERE restaurant_id = ?"
+        )
+        .bind(&self.id)
+        .fetch_one(pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// 별점 분포 (1~5)
+    async fn rating_distribution(&self, ctx: &Context<'_>) -> Result<Vec<RatingCount>> {
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let counts: Vec<(i32, i64)> = sqlx::query_as(
+            "SELECT MIN(MAX(CAST(ROUND(rating) AS INTEGER), 1), 5) AS stars, COUNT(*)
+             FROM feed_posts
+             WHERE restaurant_id = ?
+             GROUP BY stars"
+        )
+        .bind(&self.id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rating_distribution(&counts))
+    }
+
+    /// 최신 리뷰 (차단 관계인 사용자의 포스트 제외)
+    async fn latest_posts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10)] limit: i64,
+    ) -> Result<Vec<FeedPost>> {
+        let pool = ctx.data::<sqlx::SqlitePool>()?;
+        let current_user_id = ctx.data_opt::<String>();
+
+        let query = format!(
+            "SELECT * FROM feed_posts
+             WHERE restaurant_id = ? AND author_id NOT IN ({})
+             ORDER BY created_at DESC LIMIT ?",
+            BLOCKED_USER_IDS_SQL
+        );
+
+        let posts = sqlx::query_as::<_, FeedPost>(&query)
+            .bind(&self.id)
+            .bind(current_user_id)
+            .bind(current_user_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+        Ok(posts)
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.to_rfc3339()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restaurant(address: Option<&str>, coordinates: Option<(f64, f64)>) -> Restaurant {
+        Restaurant {
+            id: Uuid::new_v4().to_string(),
+            name: "바다횟집".to_string(),
+            normalized_name: normalize_name("바다횟집"),
+            address: address.map(str::to_string),
+            latitude: coordinates.map(|(lat, _)| lat),
+            longitude: coordinates.map(|(_, lng)| lng),
+            categories: "[]".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(
+            parse_location("서초구 방배동  바다횟집"),
+            ("바다횟집".to_string(), Some("서초구 방배동".to_string()))
+        );
+        assert_eq!(
+            parse_location("테헤란로 123 Blue Bottle"),
+            ("Blue Bottle".to_string(), Some("테헤란로 123".to_string()))
+        );
+        assert_eq!(parse_location("스타벅스 강남점"), ("스타벅스 강남점".to_string(), None));
+        assert_eq!(parse_location("신사동"), ("신사동".to_string(), None));
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Blue Bottle"), normalize_name("blue-bottle"));
+        assert_eq!(normalize_name(" 바다 횟집! "), "바다횟집");
+    }
+
+    #[test]
+    fn test_best_match() {
+        let near = restaurant(None, Some((37.4813, 126.9975)));
+        let far = restaurant(None, Some((35.1796, 129.0756)));
+        let other_address = restaurant(Some("부산 해운대구"), None);
+        let candidates = vec![far, other_address, near.clone()];
+
+        let matched = Restaurant::best_match(&candidates, None, Some((37.4815, 126.9977)));
+        assert_eq!(matched.map(|r| r.id.as_str()), Some(near.id.as_str()));
+
+        let matched = Restaurant::best_match(&candidates[1..2], Some("서초구 방배동"), None);
+        assert!(matched.is_none());
+
+        // 비교할 주소/좌표가 없으면 이름만으로 같은 식당
+        assert!(Restaurant::best_match(&candidates[..1], None, None).is_some());
+    }
+
+    #[test]
+    fn test_rating_distribution() {
+        let distribution = rating_distribution(&[(5, 3), (2, 1)]);
+        let counts: Vec<i64> = distribution.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![0, 1, 0, 0, 3]);
+    }
+}
@@ -1,8 +1,11 @@
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::schema::loaders::{FriendIdsLoader, UserLoader};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Enum, Copy, sqlx::Type)]
 #[sqlx(rename_all = "UPPERCASE")]
 pub enum UserStatus {
@@ -22,6 +25,8 @@ pub struct User {
     pub password_hash: Option<String>,  // 카카오 로그인 시 NULL
     pub email: Option<String>,  // 카카오 로그인 시 카카오 이메일
     pub name: String,
+    /// 검색용 닉네임 (선택)
+    pub nickname: Option<String>,
     pub initial: String,
     pub profile_image: Option<String>,
     pub status: UserStatus,
@@ -44,6 +49,52 @@ impl User {
     async fn updated_at(&self) -> String {
         self.updated_at.to_rfc3339()
     }
+
+    /// 현재 사용자와의 공통 친구 (이름 순)
+    async fn mutual_friends(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: usize,
+    ) -> Result<Vec<User>> {
+        let mutual_ids = self.mutual_friend_ids(ctx).await?;
+        if mutual_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let mut friends: Vec<User> = loader.load_many(mutual_ids).await?.into_values().collect();
+
+        friends.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        friends.truncate(limit);
+        Ok(friends)
+    }
+
+    /// 현재 사용자와의 공통 친구 수
+    async fn mutual_friend_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        Ok(self.mutual_friend_ids(ctx).await?.len() as i64)
+    }
+}
+
+impl User {
+    /// 현재 사용자와 이 사용자의 친구 ID 교집합 (비로그인 또는 본인이면 빈 목록)
+    async fn mutual_friend_ids(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        let Some(viewer_id) = ctx.data_opt::<String>() else {
+            return Ok(vec![]);
+        };
+
+        if viewer_id == &self.id {
+            return Ok(vec![]);
+        }
+
+        // 목록의 모든 사용자 조회를 FriendIdsLoader가 한 번의 캐시 조회 + 쿼리로 묶음
+        let loader = ctx.data::<DataLoader<FriendIdsLoader>>()?;
+        let mut friend_ids = loader.load_many([viewer_id.clone(), self.id.clone()]).await?;
+
+        let viewer_friend_ids = friend_ids.remove(viewer_id).unwrap_or_default();
+        let friend_ids = friend_ids.remove(&self.id).unwrap_or_default();
+
+        Ok(crate::models::Friendship::mutual_ids(&viewer_friend_ids, &friend_ids))
+    }
 }
 
 #[derive(Debug, InputObject)]
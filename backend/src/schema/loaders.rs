@@ -0,0 +1,254 @@
+use async_graphql::dataloader::Loader;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cache::FriendCache;
+use crate::models::User;
+
+/// `IN (?, ?, ...)` 절에 사용할 플레이스홀더
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(",")
+}
+
+/// 사용자 배치 로더
+///
+/// FeedPost/Comment 작성자, 친구 요청 당사자를 요청당 한 번의 `IN (...)` 쿼리로 조회
+pub struct UserLoader {
+    pool: SqlitePool,
+}
+
+impl UserLoader {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Loader<String> for UserLoader {
+    type Value = User;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let query = format!("SELECT * FROM users WHERE id IN ({})", placeholders(keys.len()));
+
+        let mut query_builder = sqlx::query_as::<_, User>(&query);
+        for key in keys {
+            query_builder = query_builder.bind(key);
+        }
+
+        let users = query_builder.fetch_all(&self.pool).await?;
+
+        Ok(users.into_iter().map(|user| (user.id.clone(), user)).collect())
+    }
+}
+
+/// 사용자별 친구 ID 배치 로더 (공통 친구 계산용)
+///
+/// FriendCache에 있는 사용자는 캐시에서 한 번에 읽고, 나머지는 한 번의 쿼리로 조회한 뒤 캐시에 저장
+pub struct FriendIdsLoader {
+    pool: SqlitePool,
+    cache: FriendCache,
+}
+
+impl FriendIdsLoader {
+    pub fn new(pool: SqlitePool, cache: FriendCache) -> Self {
+        Self { pool, cache }
+    }
+}
+
+impl Loader<String> for FriendIdsLoader {
+    type Value = Vec<String>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let mut result = self.cache.get_many_friend_ids(keys).await.unwrap_or_default();
+
+        let missing: Vec<&String> = keys.iter().filter(|key| !result.contains_key(*key)).collect();
+        if missing.is_empty() {
+            return Ok(result);
+        }
+
+        let missing_placeholders = placeholders(missing.len());
+        let query = format!(
+            "SELECT user_id, friend_id FROM friendships WHERE user_id IN ({0}) OR friend_id IN ({0})",
+            missing_placeholders
+        );
+
+        let mut query_builder = sqlx::query_as::<_, (String, String)>(&query);
+        for key in missing.iter().chain(&missing) {
+            query_builder = query_builder.bind(*key);
+        }
+        let rows = query_builder.fetch_all(&self.pool).await?;
+
+        // 친구가 없는 사용자도 빈 목록으로 캐시
+        let mut loaded: HashMap<String, Vec<String>> = missing.iter().map(|key| ((*key).clone(), Vec::new())).collect();
+        for (user_id, friend_id) in rows {
+            if let Some(friend_ids) = loaded.get_mut(&user_id) {
+                friend_ids.push(friend_id.clone());
+            }
+            if let Some(friend_ids) = loaded.get_mut(&friend_id) {
+                friend_ids.push(user_id);
+            }
+        }
+
+        for (user_id, friend_ids) in &loaded {
+            if let Err(e) = self.cache.set_friend_ids(user_id, friend_ids).await {
+                log::warn!("Failed to cache friend ids for {}: {}", user_id, e);
+            }
+        }
+
+        result.extend(loaded);
+        Ok(result)
+    }
+}
+
+/// 좋아요 여부 조회 키 (조회하는 사용자, 포스트)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LikeKey {
+    pub user_id: String,
+    pub post_id: String,
+}
+
+/// 현재 사용자의 포스트 좋아요 여부 배치 로더
+pub struct LikedByViewerLoader {
+    pool: SqlitePool,
+}
+
+impl LikedByViewerLoader {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Loader<LikeKey> for LikedByViewerLoader {
+    type Value = bool;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[LikeKey]) -> Result<HashMap<LikeKey, Self::Value>, Self::Error> {
+        // 보통 한 요청의 조회자는 한 명이므로 사용자별로 묶어서 조회
+        let mut post_ids_by_user: HashMap<&str, Vec<&str>> = HashMap::new();
+        for key in keys {
+            post_ids_by_user
+                .entry(key.user_id.as_str())
+                .or_default()
+                .push(key.post_id.as_str());
+        }
+
+        let mut result: HashMap<LikeKey, bool> = keys.iter().map(|key| (key.clone(), false)).collect();
+
+        for (user_id, post_ids) in post_ids_by_user {
+            let query = format!(
+                "SELECT post_id FROM post_likes WHERE user_id = ? AND post_id IN ({})",
+                placeholders(post_ids.len())
+            );
+
+            let mut query_builder = sqlx::query_scalar::<_, String>(&query).bind(user_id);
+            for post_id in &post_ids {
+                query_builder = query_builder.bind(*post_id);
+            }
+
+            for post_id in query_builder.fetch_all(&self.pool).await? {
+                result.insert(
+                    LikeKey {
+                        user_id: user_id.to_string(),
+                        post_id,
+                    },
+                    true,
+                );
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// 멘션 조회용 행 (댓글 ID + 멘션된 사용자)
+#[derive(sqlx::FromRow)]
+struct MentionRow {
+    comment_id: String,
+    #[sqlx(flatten)]
+    user: User,
+}
+
+/// 댓글별 멘션된 사용자 배치 로더
+pub struct MentionsLoader {
+    pool: SqlitePool,
+}
+
+impl MentionsLoader {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl Loader<String> for MentionsLoader {
+    type Value = Vec<User>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let query = format!(
+            "SELECT cm.comment_id, u.* FROM users u
+             INNER JOIN comment_mentions cm ON u.id = cm.mentioned_user_id
+             WHERE cm.comment_id IN ({})",
+            placeholders(keys.len())
+        );
+
+        let mut query_builder = sqlx::query_as::<_, MentionRow>(&query);
+        for key in keys {
+            query_builder = query_builder.bind(key);
+        }
+
+        let mut result: HashMap<String, Vec<User>> = HashMap::new();
+        for row in query_builder.fetch_all(&self.pool).await? {
+            result.entry(row.comment_id).or_default().push(row.user);
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_friend_ids_loader_loads_misses_in_one_batch() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        for id in ["a", "b", "c", "d"] {
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, name, initial)
+                 VALUES (?, ? || '@example.com', 'x', ?, 'U')"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (user_id, friend_id) in [("a", "b"), ("a", "c"), ("b", "c")] {
+            sqlx::query("INSERT INTO friendships (user_id, friend_id) VALUES (?, ?)")
+                .bind(user_id)
+                .bind(friend_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        // 캐시에 연결할 수 없으면 모두 미스로 보고 DB에서 조회
+        let cache = FriendCache::new("redis://127.0.0.1:1").unwrap();
+        let loader = FriendIdsLoader::new(pool, cache);
+
+        let keys: Vec<String> = ["a", "b", "d"].into_iter().map(String::from).collect();
+        let mut result = loader.load(&keys).await.unwrap();
+        for friend_ids in result.values_mut() {
+            friend_ids.sort();
+        }
+
+        assert_eq!(result["a"], vec!["b", "c"]);
+        assert_eq!(result["b"], vec!["a", "c"]);
+        assert!(result["d"].is_empty());
+    }
+}
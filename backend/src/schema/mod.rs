@@ -1,13 +1,47 @@
 pub mod query;
 pub mod mutation;
+pub mod subscription;
+pub mod pagination;
+pub mod loaders;
 
-use async_graphql::{EmptySubscription, Schema};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::Schema;
+use sqlx::SqlitePool;
 use query::QueryRoot;
 use mutation::MutationRoot;
+use subscription::SubscriptionRoot;
+use loaders::{FriendIdsLoader, LikedByViewerLoader, MentionsLoader, UserLoader};
 
-pub type AppSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+use crate::cache::FriendCache;
+use crate::events::EventBus;
+use crate::search::{SearchIndexer, SearchRouter};
+use crate::storage::{ImageWorker, Storage};
 
-pub fn create_schema() -> AppSchema {
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
-        .finish()
+pub type AppSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+pub fn create_schema(
+    pool: SqlitePool,
+    event_bus: EventBus,
+    friend_cache: FriendCache,
+    search_router: SearchRouter,
+    search_indexer: Option<SearchIndexer>,
+    storage: Storage,
+    image_worker: ImageWorker,
+) -> AppSchema {
+    let builder = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(DataLoader::new(UserLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(LikedByViewerLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(MentionsLoader::new(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(FriendIdsLoader::new(pool.clone(), friend_cache.clone()), tokio::spawn))
+        .data(pool)
+        .data(event_bus)
+        .data(friend_cache)
+        .data(search_router)
+        .data(storage)
+        .data(image_worker);
+
+    match search_indexer {
+        Some(search_indexer) => builder.data(search_indexer).finish(),
+        None => builder.finish(),
+    }
 }
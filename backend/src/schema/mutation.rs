@@ -3,19 +3,33 @@ use chrono::Utc;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
-use crate::auth::{hash_password, verify_password};
+use crate::auth::{hash_password, is_admin, verify_password};
 use crate::auth::jwt::create_jwt;
 use crate::auth::oauth::{KakaoOAuthClient, StateManager, TokenEncryption};
 use crate::models::{
     AuthPayload, Comment, CreateCommentInput, CreateFeedPostInput, CreateUserInput,
-    FeedPost, LoginInput, User, UserStatus, KakaoLoginUrl, KakaoLoginInput,
-    CreateOAuthProvider, OAuthProvider, log_success, log_failure,
+    FeedPost, LoginInput, UpdateFeedPostInput, User, UserStatus, KakaoLoginUrl, KakaoLoginInput,
+    CreateOAuthProvider, OAuthProvider, PostLikeCount, log_success, log_failure,
+    CreateNotification, Notification, NotificationKind, Restaurant, Category,
+    ImageUpload, ImageUploadStatus, ImageUploadTicket, ImageStatus, PostImageStatus,
 };
-use crate::search::SearchService;
+use crate::events::{AppEvent, EventBus};
+use crate::search::outbox::OutboxEntry;
+use crate::search::reindex::ReindexReport;
+use crate::search::suggest::chosung;
+use crate::search::{SearchIndexer, SearchRouter};
 use crate::session::{generate_session_id, RedisSessionStore, Session};
+use crate::storage::jobs::ImageJob;
+use crate::storage::metadata::{parse_taken_at, PhotoMetadata};
+use crate::storage::pipeline::{delete_image_variants, upload_image_variants};
+use crate::storage::upload::{max_image_bytes, read_image, validate_image, ImageMime};
+use crate::storage::{ImageWorker, Storage, StorageBackend};
 
 pub struct MutationRoot;
 
+/// 직접 업로드 URL 유효 시간 (15분)
+const IMAGE_UPLOAD_URL_EXPIRES_SECS: u64 = 15 * 60;
+
 impl MutationRoot {
     /// 친구 요청 수락 내부 로직
     async fn accept_friend_request_internal(&self, ctx: &Context<'_>, request_id: String) -> Result<bool> {
@@ -70,9 +84,85 @@ impl MutationRoot {
             let _ = cache.invalidate_both_users(&request.requester_id, &request.addressee_id).await;
         }
 
+        // 요청을 보낸 사용자에게 수락 알림
+        self.notify(pool, CreateNotification::friend_request(
+            NotificationKind::FriendRequestAccepted,
+            &request.requester_id,
+            &request.addressee_id,
+            &request.id,
+        ))
+        .await;
+
         Ok(true)
     }
 
+    /// 알림 생성 (실패해도 원래 요청은 성공 처리)
+    async fn notify(&self, pool: &SqlitePool, input: CreateNotification) {
+        if let Err(e) = Notification::create(pool, input).await {
+            log::warn!("Failed to create notification: {}", e);
+        }
+    }
+
+    /// 검색 아웃박스 기록 커밋 후 인덱싱 워커 깨우기 (없으면 주기적 확인으로 처리됨)
+    fn wake_indexer(&self, ctx: &Context<'_>) {
+        if let Ok(indexer) = ctx.data::<SearchIndexer>() {
+            indexer.wake();
+        }
+    }
+
+    /// 이미지 워커 깨우기 (작업 기록 커밋 후)
+    fn wake_image_worker(&self, ctx: &Context<'_>) {
+        if let Ok(worker) = ctx.data::<ImageWorker>() {
+            worker.wake();
+        }
+    }
+
+    /// 포스트 이미지 읽기 (multipart 업로드 우선, 없으면 이전 방식의 데이터 URI)
+    ///
+    /// 최대 크기까지만 읽고 파일 시그니처로 JPEG/PNG/WebP인지 확인
+    async fn read_post_image(
+        &self,
+        ctx: &Context<'_>,
+        input: &CreateFeedPostInput,
+    ) -> Result<Option<(Vec<u8>, ImageMime)>> {
+        let max_bytes = max_image_bytes();
+
+        if let Some(upload) = &input.image {
+            let upload = upload.value(ctx)?;
+            let image = tokio::task::spawn_blocking(move || read_image(upload.into_read(), max_bytes))
+                .await
+                .map_err(|e| format!("Failed to read upload: {}", e))??;
+            return Ok(Some(image));
+        }
+
+        if let Some(data_uri) = &input.food_image {
+            let image_data = crate::storage::ImageProcessor::decode_data_uri(data_uri)
+                .map_err(|e| format!("Failed to decode image: {}", e))?;
+            let mime = validate_image(&image_data, max_bytes)?;
+            return Ok(Some((image_data, mime)));
+        }
+
+        Ok(None)
+    }
+
+    /// 작성자 본인의 포스트 조회 (수정/삭제 권한 확인)
+    async fn find_own_post(&self, pool: &SqlitePool, post_id: &str, user_id: &str) -> Result<FeedPost> {
+        let post: Option<FeedPost> = sqlx::query_as(
+            "SELECT * FROM feed_posts WHERE id = ?"
+        )
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await?;
+
+        let post = post.ok_or("Post not found")?;
+
+        if post.author_id != user_id {
+            return Err("Unauthorized".into());
+        }
+
+        Ok(post)
+    }
+
     /// 친구 통계 업데이트
     async fn update_friend_stats(&self, user_id: &str, pool: &SqlitePool) -> Result<()> {
         // 친구 수 계산
@@ -147,21 +237,28 @@ impl MutationRoot {
         let now = Utc::now();
 
         // 사용자 생성
+        let mut tx = pool.begin().await?;
         sqlx::query(
-            "INSERT INTO users (id, email, password_hash, name, initial, status, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO users (id, email, password_hash, name, name_chosung, initial, status, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&user_id)
         .bind(&input.email)
         .bind(&password_hash)
         .bind(&input.name)
+        .bind(chosung(&input.name))
         .bind(&initial)
         .bind(UserStatus::Online)
         .bind(now)
         .bind(now)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
+        // 사용자 검색 인덱스에 추가
+        OutboxEntry::enqueue_user(&mut tx, &user_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
         // 생성된 사용자 조회
         let user: User = sqlx::query_as(
             "SELECT * FROM users WHERE id = ?"
@@ -263,6 +360,98 @@ impl MutationRoot {
         Ok(true)
     }
 
+    /// 이미지 직접 업로드 URL 발급
+    ///
+    /// 클라이언트는 uploadUrl로 원본을 PUT한 뒤 finalizeImageUpload를 호출
+    async fn request_image_upload(
+        &self,
+        ctx: &Context<'_>,
+        content_type: String,
+        size: Option<i64>,
+    ) -> Result<ImageUploadTicket> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let mime = ImageMime::from_content_type(&content_type)
+            .ok_or("Unsupported image type (JPEG, PNG, WebP only)")?;
+
+        let max_bytes = max_image_bytes();
+        if size.is_some_and(|size| size < 0 || size as u64 > max_bytes as u64) {
+            return Err(format!("Image must be at most {} bytes", max_bytes).into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let storage = ctx.data::<Storage>()?;
+
+        let upload_id = Uuid::new_v4().to_string();
+        let expires_at = Utc::now() + chrono::Duration::seconds(IMAGE_UPLOAD_URL_EXPIRES_SECS as i64);
+        let upload = ImageUpload::create(pool, &upload_id, user_id, mime.content_type(), expires_at).await?;
+
+        let upload_url = storage
+            .presigned_upload_url(&upload.original_key, &upload.content_type, IMAGE_UPLOAD_URL_EXPIRES_SECS)
+            .await
+            .map_err(|e| format!("Failed to create upload URL: {}", e))?;
+
+        Ok(ImageUploadTicket {
+            upload_id: upload.id,
+            upload_url,
+            method: "PUT".to_string(),
+            content_type: upload.content_type,
+            expires_at: upload.expires_at.to_rfc3339(),
+        })
+    }
+
+    /// 직접 업로드한 원본 처리 (변형 생성 후 원본 삭제)
+    ///
+    /// extractMetadata에 동의하면 EXIF의 촬영 시각/좌표를 추천 값으로 반환 (변형에는 메타데이터가 남지 않음)
+    /// 이미 처리된 업로드는 그대로 반환
+    async fn finalize_image_upload(
+        &self,
+        ctx: &Context<'_>,
+        upload_id: String,
+        #[graphql(default = false)] extract_metadata: bool,
+    ) -> Result<ImageUpload> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let upload = ImageUpload::find_for_user(pool, &upload_id, user_id)
+            .await?
+            .ok_or("Image upload not found")?;
+
+        if upload.status == ImageUploadStatus::Ready {
+            return Ok(upload);
+        }
+
+        // 업로드 URL이 만료된 업로드는 확정 불가 (주기적으로 정리됨)
+        if upload.expires_at < Utc::now() {
+            return Err("Image upload has expired".into());
+        }
+
+        let storage = ctx.data::<Storage>()?;
+        let max_bytes = max_image_bytes();
+        let image_data = storage
+            .download(&upload.original_key, max_bytes)
+            .await
+            .map_err(|e| format!("Failed to read uploaded image: {}", e))?;
+        validate_image(&image_data, max_bytes)?;
+
+        let metadata = if extract_metadata {
+            PhotoMetadata::read(&image_data)
+        } else {
+            PhotoMetadata::default()
+        };
+
+        let image_urls = upload_image_variants(storage, user_id, &upload.id, image_data).await?;
+        let upload = ImageUpload::mark_ready(pool, &upload.id, &image_urls, &metadata).await?;
+
+        if let Err(e) = storage.delete(&upload.original_key).await {
+            log::warn!("Failed to delete original upload {}: {}", upload.original_key, e);
+        }
+
+        Ok(upload)
+    }
+
     /// 피드 포스트 생성
     async fn create_feed_post(
         &self,
@@ -274,97 +463,201 @@ impl MutationRoot {
 
         let pool = ctx.data::<SqlitePool>()?;
 
+        let mut coordinates = crate::models::geo::validate_optional_coordinates(input.latitude, input.longitude)?;
+        let taken_at = input.taken_at.as_deref().map(parse_taken_at).transpose()?;
+
+        let restaurant = match input.restaurant_id {
+            Some(ref restaurant_id) => Some(
+                Restaurant::find_by_id(pool, restaurant_id)
+                    .await?
+                    .ok_or("Restaurant not found")?,
+            ),
+            None => None,
+        };
+
+        // 좌표가 없으면 식당 좌표 사용
+        if coordinates.is_none() {
+            if let Some(Restaurant { latitude: Some(lat), longitude: Some(lng), .. }) = &restaurant {
+                coordinates = Some((*lat, *lng));
+            }
+        }
+
         let post_id = Uuid::new_v4().to_string();
         let now = Utc::now();
         let tags_json = serde_json::to_string(&input.tags)?;
 
-        // 엔터프라이즈 이미지 처리: 다중 포맷/해상도 생성 + S3 업로드
-        let image_urls_json = if let Some(ref data_uri) = input.food_image {
-            use crate::storage::{ImageProcessor, ImageVariant, OutputFormat};
-            use crate::models::{ImageUrls, ImageFormatUrls};
+        if input.image_id.is_some() && (input.image.is_some() || input.food_image.is_some()) {
+            return Err("imageId cannot be combined with image or foodImage".into());
+        }
+
+        // 원본만 저장소에 올리고 다중 포맷/해상도 변형은 이미지 워커가 생성 (응답을 기다리게 하지 않음)
+        let storage = ctx.data::<Storage>()?;
+        let source_key = match self.read_post_image(ctx, &input).await? {
+            Some((image_data, mime)) => {
+                let source_key = ImageJob::source_key_for(user_id, &post_id);
+                storage
+                    .upload(&source_key, image_data, mime.content_type())
+                    .await
+                    .map_err(|e| format!("Failed to upload image: {}", e))?;
 
-            // Base64 디코딩
-            let image_data = ImageProcessor::decode_data_uri(data_uri)
-                .map_err(|e| format!("Failed to decode image: {}", e))?;
+                Some(source_key)
+            }
+            None => None,
+        };
+
+        // 포스트, 이미지 작업, 검색 아웃박스를 같은 트랜잭션으로 기록
+        let result = async {
+            let mut tx = pool.begin().await?;
+
+            // 직접 업로드한 이미지 연결 (다른 포스트에서 이미 사용했거나 처리 전이면 실패)
+            let image_urls_json = match input.image_id {
+                Some(ref image_id) => Some(
+                    ImageUpload::attach_to_post(&mut tx, image_id, user_id, &post_id)
+                        .await?
+                        .ok_or("Image upload not found or not finalized")?,
+                ),
+                None => None,
+            };
+
+            let image_status = if image_urls_json.is_some() {
+                Some(ImageStatus::Ready)
+            } else {
+                source_key.as_ref().map(|_| ImageStatus::Processing)
+            };
 
-            // 모든 변형 생성 (3 해상도 x 2 포맷 = 6개)
-            let processed_images = ImageProcessor::process_all_variants(&image_data)
-                .map_err(|e| format!("Failed to process images: {}", e))?;
+            sqlx::query(
+                "INSERT INTO feed_posts
+                 (id, author_id, title, content, location, latitude, longitude, restaurant_id, rating, image_urls, image_status, taken_at, category, tags, likes, comments_count, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, ?)"
+            )
+            .bind(&post_id)
+            .bind(user_id)
+            .bind(&input.title)
+            .bind(&input.content)
+            .bind(&input.location)
+            .bind(coordinates.map(|(lat, _)| lat))
+            .bind(coordinates.map(|(_, lng)| lng))
+            .bind(&input.restaurant_id)
+            .bind(input.rating)
+            .bind(&image_urls_json)
+            .bind(image_status)
+            .bind(taken_at)
+            .bind(input.category)
+            .bind(&tags_json)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
 
-            // S3 클라이언트
-            let s3_client = ctx.data::<crate::storage::S3Client>()?;
+            if let Some(ref source_key) = source_key {
+                ImageJob::enqueue(&mut tx, &post_id, user_id, &post_id, source_key).await?;
+            }
 
-            // 각 변형별로 S3에 업로드
-            let mut url_map = std::collections::HashMap::new();
+            // Elasticsearch에 인덱싱
+            OutboxEntry::enqueue(&mut tx, &post_id).await?;
+            tx.commit().await?;
+            Ok::<_, Error>(())
+        }
+        .await;
+
+        // 포스트가 기록되지 않았으면 올려둔 원본 삭제
+        if let Err(e) = result {
+            if let Some(ref source_key) = source_key {
+                if let Err(delete_err) = storage.delete(source_key).await {
+                    log::warn!("Failed to delete orphaned original image {}: {}", source_key, delete_err);
+                }
+            }
+            return Err(e);
+        }
 
-            for img in processed_images {
-                let key = ImageProcessor::generate_s3_key(
-                    user_id,
-                    &post_id,
-                    img.variant,
-                    img.format,
-                );
+        self.wake_indexer(ctx);
+        self.wake_image_worker(ctx);
 
-                let url = s3_client.upload(&key, img.data, img.format.content_type())
-                    .await
-                    .map_err(|e| format!("Failed to upload to S3: {}", e))?;
+        let post: FeedPost = sqlx::query_as(
+            "SELECT * FROM feed_posts WHERE id = ?"
+        )
+        .bind(&post_id)
+        .fetch_one(pool)
+        .await?;
 
-                url_map.insert((img.variant, img.format), url);
+        if let Some(restaurant) = restaurant {
+            if let Err(e) = restaurant.add_category(pool, input.category).await {
+                log::warn!("Failed to update restaurant categories: {}", e);
             }
+        }
 
-            // ImageUrls 구조체 생성
-            let image_urls = ImageUrls {
-                thumbnail: ImageFormatUrls {
-                    webp: url_map.get(&(ImageVariant::Thumbnail, OutputFormat::WebP))
-                        .ok_or("Missing thumbnail WebP")?
-                        .clone(),
-                    jpeg: url_map.get(&(ImageVariant::Thumbnail, OutputFormat::Jpeg))
-                        .ok_or("Missing thumbnail JPEG")?
-                        .clone(),
-                },
-                medium: ImageFormatUrls {
-                    webp: url_map.get(&(ImageVariant::Medium, OutputFormat::WebP))
-                        .ok_or("Missing medium WebP")?
-                        .clone(),
-                    jpeg: url_map.get(&(ImageVariant::Medium, OutputFormat::Jpeg))
-                        .ok_or("Missing medium JPEG")?
-                        .clone(),
-                },
-                large: ImageFormatUrls {
-                    webp: url_map.get(&(ImageVariant::Large, OutputFormat::WebP))
-                        .ok_or("Missing large WebP")?
-                        .clone(),
-                    jpeg: url_map.get(&(ImageVariant::Large, OutputFormat::Jpeg))
-                        .ok_or("Missing large JPEG")?
-                        .clone(),
-                },
-            };
+        Ok(post)
+    }
 
-            Some(serde_json::to_string(&image_urls)?)
-        } else {
-            None
+    /// 자유 입력 위치로 식당 조회 (같은 이름/장소가 없으면 생성)
+    ///
+    /// 반환된 ID를 createFeedPost의 restaurantId로 사용
+    async fn match_restaurant(
+        &self,
+        ctx: &Context<'_>,
+        location: String,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        category: Option<Category>,
+    ) -> Result<Restaurant> {
+        ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let coordinates = crate::models::geo::validate_optional_coordinates(latitude, longitude)?;
+
+        Restaurant::match_or_create(pool, &location, coordinates, category)
+            .await
+            .map_err(|e| format!("Failed to match restaurant: {}", e).into())
+    }
+
+    /// 피드 포스트 수정 (작성자만 가능)
+    async fn update_feed_post(
+        &self,
+        ctx: &Context<'_>,
+        post_id: String,
+        input: UpdateFeedPostInput,
+    ) -> Result<FeedPost> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let post = self.find_own_post(pool, &post_id, user_id).await?;
+
+        if let Some(rating) = input.rating {
+            if !(0.0..=5.0).contains(&rating) {
+                return Err("Rating must be between 0 and 5".into());
+            }
+        }
+
+        let tags_json = match input.tags {
+            Some(ref tags) => serde_json::to_string(tags)?,
+            None => post.tags.clone(),
         };
 
+        let mut tx = pool.begin().await?;
+
         sqlx::query(
-            "INSERT INTO feed_posts
-             (id, author_id, title, content, location, rating, food_image, image_urls, category, tags, likes, comments_count, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, ?)"
+            "UPDATE feed_posts
+             SET title = ?, content = ?, location = ?, rating = ?, category = ?, tags = ?, updated_at = ?
+             WHERE id = ?"
         )
-        .bind(&post_id)
-        .bind(user_id)
-        .bind(&input.title)
-        .bind(&input.content)
-        .bind(&input.location)
-        .bind(input.rating)
-        .bind(&input.food_image)
-        .bind(&image_urls_json)
-        .bind(input.category)
+        .bind(input.title.unwrap_or(post.title))
+        .bind(input.content.unwrap_or(post.content))
+        .bind(input.location.unwrap_or(post.location))
+        .bind(input.rating.unwrap_or(post.rating))
+        .bind(input.category.unwrap_or(post.category))
         .bind(&tags_json)
-        .bind(now)
-        .bind(now)
-        .execute(pool)
+        .bind(Utc::now())
+        .bind(&post_id)
+        .execute(&mut *tx)
         .await?;
 
+        // Elasticsearch 문서 갱신
+        OutboxEntry::enqueue(&mut tx, &post_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
         let post: FeedPost = sqlx::query_as(
             "SELECT * FROM feed_posts WHERE id = ?"
         )
@@ -372,14 +665,80 @@ impl MutationRoot {
         .fetch_one(pool)
         .await?;
 
-        // Elasticsearch에 인덱싱
-        if let Ok(search_service) = ctx.data::<SearchService>() {
-            if let Err(e) = search_service.index_post(&post).await {
-                log::warn!("Failed to index post in Elasticsearch: {}", e);
+        Ok(post)
+    }
+
+    /// 피드 포스트 삭제 (작성자만 가능)
+    ///
+    /// SQLite의 좋아요/댓글/멘션, Elasticsearch 문서, S3 이미지 변형을 함께 정리
+    async fn delete_feed_post(&self, ctx: &Context<'_>, post_id: String) -> Result<bool> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let post = self.find_own_post(pool, &post_id, user_id).await?;
+
+        // 직접 업로드한 이미지는 업로드 ID, 그 외에는 포스트 ID를 이미지 ID로 사용
+        let image_id = ImageUpload::find_id_for_post(pool, &post_id)
+            .await?
+            .unwrap_or_else(|| post.id.clone());
+
+        // 연관 데이터와 포스트를 하나의 트랜잭션으로 삭제
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM image_uploads WHERE post_id = ?")
+            .bind(&post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // 아직 처리되지 않은 이미지 작업 취소
+        let pending_source_key = ImageJob::delete_for_post(&mut tx, &post_id).await?;
+
+        sqlx::query(
+            "DELETE FROM comment_mentions
+             WHERE comment_id IN (SELECT id FROM comments WHERE post_id = ?)"
+        )
+        .bind(&post_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM comments WHERE post_id = ?")
+            .bind(&post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM post_likes WHERE post_id = ?")
+            .bind(&post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM notifications WHERE post_id = ?")
+            .bind(&post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM feed_posts WHERE id = ?")
+            .bind(&post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Elasticsearch에서 제거
+        OutboxEntry::enqueue(&mut tx, &post_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
+        // 저장된 이미지 변형과 처리 대기 중이던 원본 삭제
+        let storage = ctx.data::<Storage>()?;
+        if post.image_urls.is_some() {
+            delete_image_variants(storage, &post.author_id, &image_id).await;
+        }
+        if let Some(source_key) = pending_source_key {
+            if let Err(e) = storage.delete(&source_key).await {
+                log::warn!("Failed to delete original image {}: {}", source_key, e);
             }
         }
 
-        Ok(post)
+        Ok(true)
     }
 
     /// 포스트 좋아요 토글
@@ -398,6 +757,9 @@ impl MutationRoot {
         .fetch_optional(pool)
         .await?;
 
+        // 좋아요 변경, 좋아요 수, 검색 아웃박스를 하나의 트랜잭션으로 기록
+        let mut tx = pool.begin().await?;
+
         let is_liked = if existing_like.is_some() {
             // 좋아요 취소
             sqlx::query(
@@ -405,7 +767,7 @@ impl MutationRoot {
             )
             .bind(&post_id)
             .bind(user_id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
             // 좋아요 수 감소
@@ -413,7 +775,7 @@ impl MutationRoot {
                 "UPDATE feed_posts SET likes = likes - 1 WHERE id = ?"
             )
             .bind(&post_id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
             false
@@ -426,7 +788,7 @@ impl MutationRoot {
             .bind(&post_id)
             .bind(user_id)
             .bind(now)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
             // 좋아요 수 증가
@@ -434,15 +796,121 @@ impl MutationRoot {
                 "UPDATE feed_posts SET likes = likes + 1 WHERE id = ?"
             )
             .bind(&post_id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
             true
         };
 
+        // 검색 랭킹(좋아요 수) 반영
+        OutboxEntry::enqueue(&mut tx, &post_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
+        if is_liked {
+            // 포스트 작성자에게 좋아요 알림
+            let author_id: Option<String> = sqlx::query_scalar(
+                "SELECT author_id FROM feed_posts WHERE id = ?"
+            )
+            .bind(&post_id)
+            .fetch_optional(pool)
+            .await?;
+
+            if let Some(author_id) = author_id {
+                self.notify(pool, CreateNotification::post_liked(&author_id, user_id, &post_id)).await;
+            }
+        } else if let Err(e) = Notification::delete_post_like(pool, user_id, &post_id).await {
+            // 아직 남아있는 좋아요 알림 제거
+            log::warn!("Failed to remove like notification: {}", e);
+        }
+
+        // 좋아요 수 변경 알림
+        if let Ok(event_bus) = ctx.data::<EventBus>() {
+            let likes: i64 = sqlx::query_scalar("SELECT likes FROM feed_posts WHERE id = ?")
+                .bind(&post_id)
+                .fetch_one(pool)
+                .await?;
+
+            event_bus.publish(AppEvent::PostLikeCountChanged(PostLikeCount { post_id, likes }));
+        }
+
         Ok(is_liked)
     }
 
+    /// 검색 인덱스 무중단 재인덱싱 (관리자 전용, 매핑 변경 후 실행)
+    async fn reindex_search(&self, ctx: &Context<'_>) -> Result<ReindexReport> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
+        }
+
+        let indexer = ctx.data::<SearchIndexer>()?;
+        indexer
+            .reindex()
+            .await
+            .map_err(|e| format!("Reindex failed: {}", e).into())
+    }
+
+    /// 사용자 검색 인덱스 무중단 재인덱싱 (관리자 전용)
+    async fn reindex_user_search(&self, ctx: &Context<'_>) -> Result<ReindexReport> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let users = ctx
+            .data::<SearchRouter>()?
+            .users()
+            .ok_or("Elasticsearch is not available")?;
+        users
+            .reindex(pool)
+            .await
+            .map_err(|e| format!("Reindex failed: {}", e).into())
+    }
+
+    /// DEAD 상태의 검색 아웃박스 항목 재시도 (관리자 전용, ids가 없으면 전체)
+    async fn retry_search_outbox(&self, ctx: &Context<'_>, ids: Option<Vec<i64>>) -> Result<i64> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let retried = OutboxEntry::retry_dead(pool, ids.as_deref()).await?;
+        self.wake_indexer(ctx);
+
+        Ok(retried as i64)
+    }
+
+    /// DEAD 상태의 이미지 처리 작업 재시도 (관리자 전용, ids가 없으면 전체)
+    async fn retry_image_jobs(&self, ctx: &Context<'_>, ids: Option<Vec<i64>>) -> Result<i64> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let jobs = ImageJob::retry_dead(pool, ids.as_deref()).await?;
+        self.wake_image_worker(ctx);
+
+        if let Ok(event_bus) = ctx.data::<EventBus>() {
+            for job in &jobs {
+                event_bus.publish(AppEvent::PostImageStatusChanged(PostImageStatus {
+                    post_id: job.post_id.clone(),
+                    status: ImageStatus::Processing,
+                    image_urls: None,
+                }));
+            }
+        }
+
+        Ok(jobs.len() as i64)
+    }
+
     /// 댓글 작성
     async fn create_comment(
         &self,
@@ -454,10 +922,36 @@ impl MutationRoot {
 
         let pool = ctx.data::<SqlitePool>()?;
 
+        // 포스트 작성자와 차단 관계이면 댓글 작성 불가
+        let post_author_id: Option<String> = sqlx::query_scalar(
+            "SELECT author_id FROM feed_posts WHERE id = ?"
+        )
+        .bind(&input.post_id)
+        .fetch_optional(pool)
+        .await?;
+
+        let post_author_id = post_author_id.ok_or("Post not found")?;
+
+        if crate::models::FriendRequest::is_blocked_between(pool, user_id, &post_author_id).await? {
+            return Err("Cannot comment on this post".into());
+        }
+
+        // 차단 관계인 사용자는 멘션 불가
+        if let Some(mentions) = &input.mentions {
+            for mentioned_user_id in mentions {
+                if crate::models::FriendRequest::is_blocked_between(pool, user_id, mentioned_user_id).await? {
+                    return Err("Cannot mention this user".into());
+                }
+            }
+        }
+
         let comment_id = Uuid::new_v4().to_string();
         let now = Utc::now();
         let is_reply = input.parent_comment_id.is_some();
 
+        // 댓글, 멘션, 댓글 수, 검색 아웃박스를 하나의 트랜잭션으로 기록
+        let mut tx = pool.begin().await?;
+
         // 댓글 생성
         sqlx::query(
             "INSERT INTO comments
@@ -472,20 +966,19 @@ impl MutationRoot {
         .bind(is_reply)
         .bind(now)
         .bind(now)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
         // 멘션 추가
-        if let Some(mentions) = input.mentions {
-            for mentioned_user_id in mentions {
-                sqlx::query(
-                    "INSERT INTO comment_mentions (comment_id, mentioned_user_id) VALUES (?, ?)"
-                )
-                .bind(&comment_id)
-                .bind(&mentioned_user_id)
-                .execute(pool)
-                .await?;
-            }
+        let mentions = input.mentions.unwrap_or_default();
+        for mentioned_user_id in &mentions {
+            sqlx::query(
+                "INSERT INTO comment_mentions (comment_id, mentioned_user_id) VALUES (?, ?)"
+            )
+            .bind(&comment_id)
+            .bind(mentioned_user_id)
+            .execute(&mut *tx)
+            .await?;
         }
 
         // 포스트의 댓글 수 증가
@@ -493,9 +986,25 @@ impl MutationRoot {
             "UPDATE feed_posts SET comments_count = comments_count + 1 WHERE id = ?"
         )
         .bind(&input.post_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
+        // 검색 랭킹(댓글 수) 반영
+        OutboxEntry::enqueue(&mut tx, &input.post_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
+        // 멘션된 사용자에게 알림
+        for mentioned_user_id in &mentions {
+            self.notify(pool, CreateNotification::comment_mention(
+                mentioned_user_id,
+                user_id,
+                &input.post_id,
+                &comment_id,
+            ))
+            .await;
+        }
+
         let comment: Comment = sqlx::query_as(
             "SELECT * FROM comments WHERE id = ?"
         )
@@ -503,6 +1012,11 @@ impl MutationRoot {
         .fetch_one(pool)
         .await?;
 
+        // 새 댓글 알림
+        if let Ok(event_bus) = ctx.data::<EventBus>() {
+            event_bus.publish(AppEvent::CommentAdded(comment.clone()));
+        }
+
         Ok(comment)
     }
 
@@ -527,22 +1041,32 @@ impl MutationRoot {
             return Err("Unauthorized".into());
         }
 
+        let mut tx = pool.begin().await?;
+
         // 댓글 삭제
         sqlx::query(
             "DELETE FROM comments WHERE id = ?"
         )
         .bind(&comment_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
+        // 댓글의 멘션 알림 삭제
+        Notification::delete_comment_mentions(&mut tx, &comment_id).await?;
+
         // 포스트의 댓글 수 감소
         sqlx::query(
             "UPDATE feed_posts SET comments_count = comments_count - 1 WHERE id = ?"
         )
         .bind(&comment.post_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
 
+        // 검색 랭킹(댓글 수) 반영
+        OutboxEntry::enqueue(&mut tx, &comment.post_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
         Ok(true)
     }
 
@@ -551,12 +1075,14 @@ impl MutationRoot {
         &self,
         ctx: &Context<'_>,
         name: Option<String>,
+        nickname: Option<String>,
         profile_image: Option<String>,
     ) -> Result<User> {
         let user_id = ctx.data_opt::<String>()
             .ok_or("Unauthorized")?;
 
         let pool = ctx.data::<SqlitePool>()?;
+        let mut tx = pool.begin().await?;
 
         if let Some(name) = name {
             let initial = name.chars().next()
@@ -564,13 +1090,29 @@ impl MutationRoot {
                 .unwrap_or_else(|| "?".to_string());
 
             sqlx::query(
-                "UPDATE users SET name = ?, initial = ?, updated_at = ? WHERE id = ?"
+                "UPDATE users SET name = ?, name_chosung = ?, initial = ?, updated_at = ? WHERE id = ?"
             )
             .bind(&name)
+            .bind(chosung(&name))
             .bind(&initial)
             .bind(Utc::now())
             .bind(user_id)
-            .execute(pool)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // 빈 문자열이면 닉네임 삭제
+        if let Some(nickname) = nickname {
+            let nickname = Some(nickname.trim().to_string()).filter(|nickname| !nickname.is_empty());
+
+            sqlx::query(
+                "UPDATE users SET nickname = ?, nickname_chosung = ?, updated_at = ? WHERE id = ?"
+            )
+            .bind(&nickname)
+            .bind(nickname.as_deref().map(chosung))
+            .bind(Utc::now())
+            .bind(user_id)
+            .execute(&mut *tx)
             .await?;
         }
 
@@ -581,10 +1123,15 @@ impl MutationRoot {
             .bind(&profile_image)
             .bind(Utc::now())
             .bind(user_id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
         }
 
+        // 사용자 검색 인덱스 갱신
+        OutboxEntry::enqueue_user(&mut tx, user_id).await?;
+        tx.commit().await?;
+        self.wake_indexer(ctx);
+
         let user: User = sqlx::query_as(
             "SELECT * FROM users WHERE id = ?"
         )
@@ -618,6 +1165,11 @@ impl MutationRoot {
             return Err("User not found".into());
         }
 
+        // 차단 관계 확인 (어느 쪽이 차단했든 요청 불가)
+        if crate::models::FriendRequest::is_blocked_between(pool, user_id, &addressee_id).await? {
+            return Err("Cannot send friend request to this user".into());
+        }
+
         // 이미 친구인지 확인
         let (uid, fid) = crate::models::Friendship::normalize_ids(user_id, &addressee_id);
         let existing_friendship: Option<(String,)> = sqlx::query_as(
@@ -678,6 +1230,31 @@ impl MutationRoot {
         // 통계 업데이트
         self.update_friend_stats(&addressee_id, pool).await?;
 
+        // 요청한 상대가 추천 목록에 남지 않도록 추천 캐시 무효화
+        if let Ok(cache) = ctx.data::<crate::cache::FriendCache>() {
+            let _ = cache.invalidate_suggestions(user_id).await;
+        }
+
+        self.notify(pool, CreateNotification::friend_request(
+            NotificationKind::FriendRequestReceived,
+            &addressee_id,
+            user_id,
+            &request_id,
+        ))
+        .await;
+
+        // 받는 사람에게 친구 요청 알림 (실시간 구독)
+        if let Ok(event_bus) = ctx.data::<EventBus>() {
+            let request: crate::models::FriendRequest = sqlx::query_as(
+                "SELECT * FROM friend_requests WHERE id = ?"
+            )
+            .bind(&request_id)
+            .fetch_one(pool)
+            .await?;
+
+            event_bus.publish(AppEvent::FriendRequestReceived(request));
+        }
+
         Ok(true)
     }
 
@@ -704,6 +1281,11 @@ impl MutationRoot {
 
         let request = request.ok_or("Friend request not found")?;
 
+        // 차단 기록(BLOCKED)을 거절로 덮어써 차단이 풀리지 않도록 대기 중인 요청만 허용
+        if request.status != crate::models::FriendRequestStatus::Pending {
+            return Err("Friend request is not pending".into());
+        }
+
         // 요청 상태 업데이트
         sqlx::query(
             "UPDATE friend_requests SET status = 'REJECTED', updated_at = ? WHERE id = ?"
@@ -785,6 +1367,118 @@ impl MutationRoot {
         Ok(true)
     }
 
+    /// 사용자 차단
+    ///
+    /// 친구 관계와 양방향 대기 중인 요청을 제거하고 BLOCKED 상태의 요청으로 기록
+    async fn block_user(&self, ctx: &Context<'_>, blocked_id: String) -> Result<bool> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        if user_id == &blocked_id {
+            return Err("Cannot block yourself".into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let blocked_exists: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM users WHERE id = ?"
+        )
+        .bind(&blocked_id)
+        .fetch_optional(pool)
+        .await?;
+
+        if blocked_exists.is_none() {
+            return Err("User not found".into());
+        }
+
+        let (uid, fid) = crate::models::Friendship::normalize_ids(user_id, &blocked_id);
+        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM friendships WHERE user_id = ? AND friend_id = ?")
+            .bind(uid)
+            .bind(fid)
+            .execute(&mut *tx)
+            .await?;
+
+        // 내가 보낸 요청은 모두, 상대가 보낸 요청은 상대의 차단 기록을 제외하고 삭제
+        sqlx::query(
+            "DELETE FROM friend_requests
+             WHERE (requester_id = ? AND addressee_id = ?)
+             OR (requester_id = ? AND addressee_id = ? AND status != 'BLOCKED')"
+        )
+        .bind(user_id)
+        .bind(&blocked_id)
+        .bind(&blocked_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO friend_requests (id, requester_id, addressee_id, status, created_at, updated_at)
+             VALUES (?, ?, ?, 'BLOCKED', ?, ?)"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&blocked_id)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        // 통계 업데이트
+        self.update_friend_stats(user_id, pool).await?;
+        self.update_friend_stats(&blocked_id, pool).await?;
+
+        // 캐시 무효화
+        if let Ok(cache) = ctx.data::<crate::cache::FriendCache>() {
+            let _ = cache.invalidate_both_users(user_id, &blocked_id).await;
+        }
+
+        Ok(true)
+    }
+
+    /// 사용자 차단 해제 (친구 관계는 복구되지 않음)
+    async fn unblock_user(&self, ctx: &Context<'_>, blocked_id: String) -> Result<bool> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let result = sqlx::query(
+            "DELETE FROM friend_requests
+             WHERE requester_id = ? AND addressee_id = ? AND status = 'BLOCKED'"
+        )
+        .bind(user_id)
+        .bind(&blocked_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err("User is not blocked".into());
+        }
+
+        Ok(true)
+    }
+
+    /// 알림 읽음 처리 (ids를 생략하면 전체 읽음), 읽음 처리된 알림 수 반환
+    async fn mark_notifications_read(
+        &self,
+        ctx: &Context<'_>,
+        ids: Option<Vec<String>>,
+    ) -> Result<i64> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let updated = Notification::mark_read(pool, user_id, ids.as_deref()).await?;
+
+        Ok(updated as i64)
+    }
+
     /// 카카오 로그인 URL 생성
     async fn generate_kakao_login_url(&self, ctx: &Context<'_>) -> Result<KakaoLoginUrl> {
         let client_id = std::env::var("KAKAO_CLIENT_ID")
@@ -926,25 +1620,34 @@ impl MutationRoot {
             let now = Utc::now();
 
             // 사용자 생성
+            let mut tx = pool.begin().await?;
             sqlx::query(
                 r#"
                 INSERT INTO users
-                (id, email, password_hash, name, initial, profile_image, status,
-                 login_method, kakao_id, created_at, updated_at)
-                VALUES (?, ?, NULL, ?, ?, ?, 'ONLINE', 'kakao', ?, ?, ?)
+                (id, email, password_hash, name, nickname, name_chosung, nickname_chosung, initial, profile_image,
+                 status, login_method, kakao_id, created_at, updated_at)
+                VALUES (?, ?, NULL, ?, ?, ?, ?, ?, ?, 'ONLINE', 'kakao', ?, ?, ?)
                 "#
             )
             .bind(&user_id)
             .bind(&email)
             .bind(&nickname)
+            .bind(&nickname)
+            .bind(chosung(&nickname))
+            .bind(chosung(&nickname))
             .bind(&initial)
             .bind(&profile_image)
             .bind(&kakao_id)
             .bind(now)
             .bind(now)
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
+            // 사용자 검색 인덱스에 추가
+            OutboxEntry::enqueue_user(&mut tx, &user_id).await?;
+            tx.commit().await?;
+            self.wake_indexer(ctx);
+
             // OAuth 프로바이더 생성
             let profile_json = serde_json::to_string(&kakao_user).ok();
             OAuthProvider::create(
@@ -0,0 +1,232 @@
+use async_graphql::connection::{Connection, Edge, OpaqueCursor};
+use async_graphql::OutputType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 페이지 크기 상한 (과도한 조회 방지)
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// 커서에 담기는 정렬 키
+///
+/// created_at으로 정렬하고 같은 시각의 행은 id로 구분 (키셋 페이지네이션)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CursorKey {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl CursorKey {
+    pub fn new(created_at: DateTime<Utc>, id: &str) -> Self {
+        Self {
+            created_at,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// 클라이언트에 노출되는 불투명 커서 (base64 인코딩된 CursorKey)
+pub type PageCursor = OpaqueCursor<CursorKey>;
+
+/// 순위로 정렬된 결과(검색)의 불투명 커서 (다음 항목의 순위 위치)
+///
+/// 순위는 친구 관계, 인덱스 갱신에 따라 바뀌므로 키셋 대신 위치로 이어서 조회
+/// (offset 기반이므로 페이지 사이에 순위가 바뀌면 항목이 중복되거나 빠질 수 있음)
+pub type RankCursor = OpaqueCursor<usize>;
+
+/// 요청된 페이지 크기 (기본값 적용 + 상한 제한)
+pub fn page_size(first: Option<usize>, default: usize) -> usize {
+    first.unwrap_or(default).min(MAX_PAGE_SIZE)
+}
+
+/// 커서를 SQL 바인딩 값으로 분해 (커서가 없으면 NULL)
+///
+/// 쿼리 조건 형식: `(? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))`
+/// (정렬도 `ORDER BY datetime(created_at) DESC, id DESC`)
+///
+/// 기본값(CURRENT_TIMESTAMP)으로 저장된 행은 `YYYY-MM-DD HH:MM:SS`, 바인딩으로 저장된 행은 RFC3339 형식이라
+/// 문자열로 비교하면 순서가 어긋나므로 양쪽을 datetime()으로 맞춤 (같은 초 안에서는 id로 구분)
+pub fn cursor_bounds(after: Option<PageCursor>) -> (Option<DateTime<Utc>>, Option<String>) {
+    match after {
+        Some(cursor) => {
+            let key = cursor.0;
+            (Some(key.created_at), Some(key.id))
+        }
+        None => (None, None),
+    }
+}
+
+/// `limit + 1`개로 조회한 결과로 Connection 생성
+///
+/// 초과분 1개가 있으면 다음 페이지가 존재하는 것으로 판단
+pub fn into_connection<R, T, F>(
+    mut rows: Vec<R>,
+    limit: usize,
+    has_previous_page: bool,
+    edge: F,
+) -> Connection<PageCursor, T>
+where
+    T: OutputType,
+    F: Fn(R) -> (CursorKey, T),
+{
+    let has_next_page = rows.len() > limit;
+    rows.truncate(limit);
+
+    let mut connection = Connection::new(has_previous_page, has_next_page);
+    connection.edges.extend(rows.into_iter().map(|row| {
+        let (key, node) = edge(row);
+        Edge::new(OpaqueCursor(key), node)
+    }));
+
+    connection
+}
+
+/// `offset`부터 `limit + 1`개로 조회한 순위 결과로 Connection 생성
+///
+/// 조회 후 사라진 항목(None)은 건너뛰지만 위치는 유지하여 다음 페이지와 겹치지 않음
+pub fn into_ranked_connection<T: OutputType>(
+    mut rows: Vec<Option<T>>,
+    offset: usize,
+    limit: usize,
+) -> Connection<RankCursor, T> {
+    let has_next_page = rows.len() > limit;
+    rows.truncate(limit);
+
+    let mut connection = Connection::new(offset > 0, has_next_page);
+    connection.edges.extend(
+        rows.into_iter()
+            .enumerate()
+            .filter_map(|(index, row)| row.map(|node| Edge::new(OpaqueCursor(offset + index + 1), node))),
+    );
+
+    connection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::connection::CursorType;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let created_at = Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+        let cursor = OpaqueCursor(CursorKey::new(created_at, "post-1"));
+
+        let encoded = cursor.encode_cursor();
+        let decoded = PageCursor::decode_cursor(&encoded).unwrap();
+
+        // 나노초까지 보존되어야 동일 시각 타이브레이크가 정확함
+        assert_eq!(decoded.0, cursor.0);
+        assert!(!encoded.contains("post-1"));
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        assert!(PageCursor::decode_cursor("not-a-cursor").is_err());
+    }
+
+    #[test]
+    fn test_page_size() {
+        assert_eq!(page_size(None, 20), 20);
+        assert_eq!(page_size(Some(5), 20), 5);
+        assert_eq!(page_size(Some(1000), 20), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_cursor_bounds() {
+        assert_eq!(cursor_bounds(None), (None, None));
+
+        let created_at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let (at, id) = cursor_bounds(Some(OpaqueCursor(CursorKey::new(created_at, "c1"))));
+        assert_eq!(at, Some(created_at));
+        assert_eq!(id.as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn test_into_connection_has_next_page() {
+        let created_at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let rows: Vec<String> = (0..4).map(|i| format!("row{}", i)).collect();
+
+        let connection = into_connection(rows, 3, true, |row| (CursorKey::new(created_at, &row), row));
+
+        assert_eq!(connection.edges.len(), 3);
+        assert!(connection.has_next_page);
+        assert!(connection.has_previous_page);
+        assert_eq!(connection.edges[2].node, "row2");
+    }
+
+    #[test]
+    fn test_into_connection_last_page() {
+        let created_at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let rows: Vec<String> = vec!["only".to_string()];
+
+        let connection = into_connection(rows, 3, false, |row| (CursorKey::new(created_at, &row), row));
+
+        assert_eq!(connection.edges.len(), 1);
+        assert!(!connection.has_next_page);
+        assert!(!connection.has_previous_page);
+    }
+
+    #[test]
+    fn test_into_ranked_connection_keeps_positions() {
+        let rows = vec![Some("a".to_string()), None, Some("c".to_string()), Some("d".to_string())];
+
+        let connection = into_ranked_connection(rows, 20, 3);
+
+        assert_eq!(connection.edges.len(), 2);
+        assert!(connection.has_next_page);
+        assert!(connection.has_previous_page);
+        assert_eq!(connection.edges[1].node, "c");
+        assert_eq!(connection.edges[1].cursor.0, 23);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_condition_with_mixed_timestamp_formats() {
+        let pool = crate::db::test_support::empty_pool().await;
+        sqlx::query("CREATE TABLE rows (id TEXT PRIMARY KEY, created_at DATETIME DEFAULT CURRENT_TIMESTAMP)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // 기본값 형식과 RFC3339 형식이 섞인 테이블
+        sqlx::query("INSERT INTO rows (id, created_at) VALUES ('a', '2024-01-01 00:00:03')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, at) in [("b", 2), ("c", 1)] {
+            sqlx::query("INSERT INTO rows (id, created_at) VALUES (?, ?)")
+                .bind(id)
+                .bind(Utc.timestamp_opt(1_704_067_200 + at, 0).unwrap())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let mut after: Option<PageCursor> = None;
+        let mut seen = Vec::new();
+        loop {
+            let (cursor_at, cursor_id) = cursor_bounds(after.take());
+            let page: Vec<(String, DateTime<Utc>)> = sqlx::query_as(
+                "SELECT id, created_at FROM rows
+                 WHERE (? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))
+                 ORDER BY datetime(created_at) DESC, id DESC
+                 LIMIT 1"
+            )
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(&cursor_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+            let Some((id, created_at)) = page.into_iter().next() else {
+                break;
+            };
+            after = Some(OpaqueCursor(CursorKey::new(created_at, &id)));
+            seen.push(id);
+        }
+
+        assert_eq!(seen, vec!["a", "b", "c"]);
+    }
+}
@@ -1,10 +1,140 @@
 use async_graphql::*;
+use async_graphql::connection::{self, Connection};
+use async_graphql::dataloader::DataLoader;
 use sqlx::SqlitePool;
-use crate::models::{User, FeedPost, Comment, Category};
-use crate::search::SearchService;
+use crate::models::{User, FeedPost, Comment, Category, Friendship, NearbyPost, Restaurant, BLOCKED_USER_IDS_SQL};
+use crate::models::geo::{self, BoundingBox};
+use crate::search::{SearchBackend, SearchRouter};
+use crate::search::service::GeoPoint;
+use crate::search::facets::{SearchFacets, SearchPostsFilter};
+use crate::search::outbox::{OutboxEntry, OutboxStatus};
+use crate::search::personalization::SearchPersonalization;
+use crate::search::suggest::SearchSuggestion;
+use crate::auth::is_admin;
+use crate::storage::jobs::{ImageJob, ImageJobStatus};
+use crate::schema::loaders::UserLoader;
+use crate::schema::pagination::{
+    cursor_bounds, into_connection, into_ranked_connection, page_size, CursorKey, PageCursor, RankCursor,
+};
 
 pub struct QueryRoot;
 
+/// ID 목록으로 포스트를 한 번에 조회 (ID → 포스트, 없는 포스트는 빠짐)
+async fn load_posts_by_ids(
+    pool: &SqlitePool,
+    ids: &[String],
+) -> Result<std::collections::HashMap<String, FeedPost>> {
+    if ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let query = format!("SELECT * FROM feed_posts WHERE id IN ({})", placeholders);
+    let mut query_builder = sqlx::query_as::<_, FeedPost>(&query);
+    for id in ids {
+        query_builder = query_builder.bind(id);
+    }
+
+    Ok(query_builder
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|post| (post.id.clone(), post))
+        .collect())
+}
+
+/// 사용자 검색 (부분 일치, 초성, 친구와 친구의 친구 우선), 순위대로 사용자 반환 (없어진 사용자는 None)
+///
+/// 현재 로그인한 사용자와 차단 관계인 사용자는 검색 결과에서 제외
+async fn search_ranked_users(ctx: &Context<'_>, query: &str, from: i64, size: i64) -> Result<Vec<Option<User>>> {
+    let pool = ctx.data::<SqlitePool>()?;
+    let search_router = ctx.data::<SearchRouter>()?;
+
+    let (friend_ids, friend_of_friend_ids, excluded_ids) = if let Some(user_id) = ctx.data_opt::<String>() {
+        let friend_ids = Friendship::load_friend_ids(ctx, user_id).await?;
+        let friend_of_friend_ids =
+            Friendship::find_friends_of_friends(pool, user_id, &friend_ids, Friendship::MAX_FRIENDS_OF_FRIENDS).await?;
+        let mut excluded_ids = crate::models::FriendRequest::find_blocked_user_ids(pool, user_id).await?;
+        excluded_ids.push(user_id.clone());
+        (friend_ids, friend_of_friend_ids, excluded_ids)
+    } else {
+        // 로그인하지 않은 경우에도 검색 가능
+        (vec![], vec![], vec![])
+    };
+
+    let user_ids = search_router
+        .search_users(query, &friend_ids, &friend_of_friend_ids, &excluded_ids, from, size)
+        .await
+        .map_err(|e| format!("User search failed: {}", e))?;
+
+    // 검색 순위를 유지하며 사용자 로드
+    let loader = ctx.data::<DataLoader<UserLoader>>()?;
+    let mut users = loader.load_many(user_ids.iter().cloned()).await?;
+
+    Ok(user_ids.iter().map(|id| users.remove(id)).collect())
+}
+
+/// 주변 포스트 조회 (SQLite 바운딩 박스 + haversine, Elasticsearch 폴백용)
+async fn find_nearby_posts_in_db(
+    pool: &SqlitePool,
+    viewer_id: Option<&String>,
+    (latitude, longitude): (f64, f64),
+    radius_km: f64,
+    category: Option<Category>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<NearbyPost>> {
+    let bbox = BoundingBox::around(latitude, longitude, radius_km);
+    let query = format!(
+        "SELECT * FROM feed_posts
+         WHERE latitude BETWEEN ? AND ? AND longitude BETWEEN ? AND ?
+           AND (? IS NULL OR category = ?)
+           AND author_id NOT IN ({})",
+        BLOCKED_USER_IDS_SQL
+    );
+
+    let candidates = sqlx::query_as::<_, FeedPost>(&query)
+        .bind(bbox.min_lat)
+        .bind(bbox.max_lat)
+        .bind(bbox.min_lng)
+        .bind(bbox.max_lng)
+        .bind(category)
+        .bind(category)
+        .bind(viewer_id)
+        .bind(viewer_id)
+        .fetch_all(pool)
+        .await?;
+
+    // 박스 모서리 영역을 걸러내고 가까운 순, 같은 거리면 최신순
+    let mut nearby: Vec<NearbyPost> = candidates
+        .into_iter()
+        .filter_map(|post| {
+            let distance_km = geo::haversine_km(latitude, longitude, post.latitude?, post.longitude?);
+            (distance_km <= radius_km).then_some(NearbyPost { post, distance_km })
+        })
+        .collect();
+
+    nearby.sort_by(|a, b| {
+        a.distance_km
+            .total_cmp(&b.distance_km)
+            .then_with(|| b.post.created_at.cmp(&a.post.created_at))
+    });
+
+    Ok(nearby
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect())
+}
+
+/// 친구 목록 커서 조회용 행 (친구가 된 시각을 커서로 사용)
+#[derive(sqlx::FromRow)]
+struct FriendRow {
+    #[sqlx(flatten)]
+    user: User,
+    friended_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[Object]
 impl QueryRoot {
     /// 현재 로그인한 사용자 정보 조회
@@ -25,6 +155,12 @@ impl QueryRoot {
         }
     }
 
+    /// 식당 조회 (평균 평점, 리뷰 수, 별점 분포, 최신 리뷰)
+    async fn restaurant(&self, ctx: &Context<'_>, id: String) -> Result<Option<Restaurant>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        Ok(Restaurant::find_by_id(pool, &id).await?)
+    }
+
     /// 사용자 ID로 사용자 정보 조회
     async fn user(&self, ctx: &Context<'_>, id: String) -> Result<Option<User>> {
         let pool = ctx.data::<SqlitePool>()?;
@@ -45,30 +181,152 @@ impl QueryRoot {
         #[graphql(default = 0)] offset: i64,
         category: Option<Category>,
     ) -> Result<Vec<FeedPost>> {
+        let current_user_id = ctx.data_opt::<String>();
         let pool = ctx.data::<SqlitePool>()?;
 
+        // 차단 관계인 사용자의 포스트는 제외
         let posts = if let Some(cat) = category {
-            sqlx::query_as::<_, FeedPost>(
-                "SELECT * FROM feed_posts WHERE category = ? ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(cat)
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?
+            let query = format!(
+                "SELECT * FROM feed_posts
+                 WHERE category = ? AND author_id NOT IN ({})
+                 ORDER BY created_at DESC LIMIT ? OFFSET ?",
+                BLOCKED_USER_IDS_SQL
+            );
+
+            sqlx::query_as::<_, FeedPost>(&query)
+                .bind(cat)
+                .bind(current_user_id)
+                .bind(current_user_id)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(pool)
+                .await?
         } else {
-            sqlx::query_as::<_, FeedPost>(
-                "SELECT * FROM feed_posts ORDER BY created_at DESC LIMIT ? OFFSET ?"
-            )
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?
+            let query = format!(
+                "SELECT * FROM feed_posts
+                 WHERE author_id NOT IN ({})
+                 ORDER BY created_at DESC LIMIT ? OFFSET ?",
+                BLOCKED_USER_IDS_SQL
+            );
+
+            sqlx::query_as::<_, FeedPost>(&query)
+                .bind(current_user_id)
+                .bind(current_user_id)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(pool)
+                .await?
         };
 
         Ok(posts)
     }
 
+    /// 주변 포스트 검색 (거리순)
+    ///
+    /// Elasticsearch geo_distance 검색, 사용 불가 시 SQLite 바운딩 박스 검색
+    async fn nearby_posts(
+        &self,
+        ctx: &Context<'_>,
+        lat: f64,
+        lng: f64,
+        #[graphql(default = 1.0)] radius_km: f64,
+        category: Option<Category>,
+        #[graphql(default = 20)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+    ) -> Result<Vec<NearbyPost>> {
+        geo::validate_coordinates(lat, lng)?;
+        if !(radius_km > 0.0 && radius_km <= geo::MAX_NEARBY_RADIUS_KM) {
+            return Err(format!("radiusKm must be between 0 and {}", geo::MAX_NEARBY_RADIUS_KM).into());
+        }
+
+        let current_user_id = ctx.data_opt::<String>();
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Elasticsearch를 쓸 수 없으면(SQLite 모드, 장애) 바로 SQLite 바운딩 박스 검색
+        if let Some(search_service) = ctx.data::<SearchRouter>().ok().and_then(|router| router.elasticsearch()) {
+            let excluded_author_ids = match current_user_id {
+                Some(user_id) => crate::models::FriendRequest::find_blocked_user_ids(pool, user_id).await?,
+                None => Vec::new(),
+            };
+
+            // Box<dyn Error>는 Send가 아니므로 await 전에 문자열로 변환
+            let es_result = search_service
+                .search_nearby_posts(GeoPoint { lat, lon: lng }, radius_km, category, &excluded_author_ids, offset, limit)
+                .await
+                .map_err(|e| e.to_string());
+
+            match es_result {
+                Ok(result) => {
+                    let ids: Vec<String> = result.hits.iter().map(|(id, _)| id.clone()).collect();
+                    let mut posts = load_posts_by_ids(pool, &ids).await?;
+
+                    // Elasticsearch 거리순 유지 (인덱스에만 남은 포스트는 제외)
+                    return Ok(result
+                        .hits
+                        .into_iter()
+                        .filter_map(|(id, distance_km)| {
+                            posts.remove(&id).map(|post| NearbyPost { post, distance_km })
+                        })
+                        .collect());
+                }
+                Err(e) => {
+                    log::warn!("Nearby search failed in Elasticsearch, falling back to SQLite: {}", e);
+                }
+            }
+        }
+
+        find_nearby_posts_in_db(pool, current_user_id, (lat, lng), radius_km, category, limit, offset).await
+    }
+
+    /// 피드 포스트 목록 조회 (커서 기반 페이지네이션)
+    ///
+    /// 새 포스트가 추가되어도 중복/누락 없이 무한 스크롤 가능
+    async fn feed_posts_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+        category: Option<Category>,
+    ) -> Result<Connection<PageCursor, FeedPost>> {
+        let current_user_id = ctx.data_opt::<String>();
+        let pool = ctx.data::<SqlitePool>()?;
+
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 20);
+            let has_previous_page = after.is_some();
+            let (cursor_at, cursor_id) = cursor_bounds(after);
+
+            // 차단 관계인 사용자의 포스트는 제외
+            let query = format!(
+                "SELECT * FROM feed_posts
+                 WHERE (? IS NULL OR category = ?)
+                 AND author_id NOT IN ({})
+                 AND (? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))
+                 ORDER BY datetime(created_at) DESC, id DESC
+                 LIMIT ?",
+                BLOCKED_USER_IDS_SQL
+            );
+
+            let posts = sqlx::query_as::<_, FeedPost>(&query)
+                .bind(category)
+                .bind(category)
+                .bind(current_user_id)
+                .bind(current_user_id)
+                .bind(cursor_at)
+                .bind(cursor_at)
+                .bind(cursor_at)
+                .bind(&cursor_id)
+                .bind((limit + 1) as i64)
+                .fetch_all(pool)
+                .await?;
+
+            Ok::<_, Error>(into_connection(posts, limit, has_previous_page, |post| {
+                (CursorKey::new(post.created_at, &post.id), post)
+            }))
+        })
+        .await
+    }
+
     /// 특정 피드 포스트 조회
     async fn feed_post(&self, ctx: &Context<'_>, id: String) -> Result<Option<FeedPost>> {
         let pool = ctx.data::<SqlitePool>()?;
@@ -101,6 +359,44 @@ impl QueryRoot {
         Ok(comments)
     }
 
+    /// 특정 포스트의 댓글 목록 조회 (커서 기반 페이지네이션, 오래된 순)
+    async fn comments_connection(
+        &self,
+        ctx: &Context<'_>,
+        post_id: String,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<PageCursor, Comment>> {
+        let pool = ctx.data::<SqlitePool>()?;
+
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 50);
+            let has_previous_page = after.is_some();
+            let (cursor_at, cursor_id) = cursor_bounds(after);
+
+            let comments = sqlx::query_as::<_, Comment>(
+                "SELECT * FROM comments
+                 WHERE post_id = ?
+                 AND (? IS NULL OR datetime(created_at) > datetime(?) OR (datetime(created_at) = datetime(?) AND id > ?))
+                 ORDER BY datetime(created_at) ASC, id ASC
+                 LIMIT ?"
+            )
+            .bind(&post_id)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(&cursor_id)
+            .bind((limit + 1) as i64)
+            .fetch_all(pool)
+            .await?;
+
+            Ok::<_, Error>(into_connection(comments, limit, has_previous_page, |comment| {
+                (CursorKey::new(comment.created_at, &comment.id), comment)
+            }))
+        })
+        .await
+    }
+
     /// 친구 목록 조회 (캐시 적용, 페이지네이션)
     async fn friends(
         &self,
@@ -138,6 +434,53 @@ impl QueryRoot {
         Ok(friends)
     }
 
+    /// 친구 목록 조회 (커서 기반 페이지네이션, 최근 친구가 된 순)
+    async fn friends_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<PageCursor, User>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 100);
+            let has_previous_page = after.is_some();
+            let (cursor_at, cursor_id) = cursor_bounds(after);
+
+            let rows = sqlx::query_as::<_, FriendRow>(
+                "SELECT u.*, f.created_at AS friended_at
+                 FROM friendships f
+                 INNER JOIN users u ON u.id = CASE
+                     WHEN f.user_id = ? THEN f.friend_id
+                     ELSE f.user_id
+                 END
+                 WHERE (f.user_id = ? OR f.friend_id = ?)
+                 AND (? IS NULL OR datetime(f.created_at) < datetime(?) OR (datetime(f.created_at) = datetime(?) AND u.id < ?))
+                 ORDER BY datetime(f.created_at) DESC, u.id DESC
+                 LIMIT ?"
+            )
+            .bind(user_id)
+            .bind(user_id)
+            .bind(user_id)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(&cursor_id)
+            .bind((limit + 1) as i64)
+            .fetch_all(pool)
+            .await?;
+
+            Ok::<_, Error>(into_connection(rows, limit, has_previous_page, |row| {
+                (CursorKey::new(row.friended_at, &row.user.id), row.user)
+            }))
+        })
+        .await
+    }
+
     /// 친구 요청 목록 조회 (받은 요청)
     async fn friend_requests(
         &self,
@@ -165,6 +508,46 @@ impl QueryRoot {
         Ok(requests)
     }
 
+    /// 받은 친구 요청 목록 조회 (커서 기반 페이지네이션)
+    async fn friend_requests_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<PageCursor, crate::models::FriendRequest>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 50);
+            let has_previous_page = after.is_some();
+            let (cursor_at, cursor_id) = cursor_bounds(after);
+
+            let requests = sqlx::query_as::<_, crate::models::FriendRequest>(
+                "SELECT * FROM friend_requests
+                 WHERE addressee_id = ? AND status = 'PENDING'
+                 AND (? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))
+                 ORDER BY datetime(created_at) DESC, id DESC
+                 LIMIT ?"
+            )
+            .bind(user_id)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(&cursor_id)
+            .bind((limit + 1) as i64)
+            .fetch_all(pool)
+            .await?;
+
+            Ok::<_, Error>(into_connection(requests, limit, has_previous_page, |request| {
+                (CursorKey::new(request.created_at, &request.id), request)
+            }))
+        })
+        .await
+    }
+
     /// 보낸 친구 요청 목록
     async fn sent_friend_requests(
         &self,
@@ -192,6 +575,69 @@ impl QueryRoot {
         Ok(requests)
     }
 
+    /// 내가 차단한 사용자 목록 (최근 차단 순)
+    async fn blocked_users(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 50)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+    ) -> Result<Vec<User>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let users = sqlx::query_as::<_, User>(
+            "SELECT u.* FROM users u
+             INNER JOIN friend_requests fr ON u.id = fr.addressee_id
+             WHERE fr.requester_id = ? AND fr.status = 'BLOCKED'
+             ORDER BY fr.created_at DESC
+             LIMIT ? OFFSET ?"
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// 친구 추천 (공통 친구 수 + 좋아요 취향 유사도 순, 사용자별 캐시)
+    async fn friend_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10)] limit: usize,
+    ) -> Result<Vec<crate::models::FriendSuggestion>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let cache = ctx.data::<crate::cache::FriendCache>().ok();
+
+        // 사용자별로 FriendCache에 캐싱 (친구 추가/삭제, 친구 요청 시 무효화)
+        let ranked = crate::models::friend_suggestion::load_friend_suggestions(pool, cache, user_id).await?;
+
+        // 점수 순서를 유지하며 사용자 정보 로드
+        let ranked: Vec<_> = ranked.into_iter().take(limit).collect();
+        let loader = ctx.data::<DataLoader<UserLoader>>()?;
+        let mut users = loader
+            .load_many(ranked.iter().map(|suggestion| suggestion.user_id.clone()))
+            .await?;
+
+        Ok(ranked
+            .into_iter()
+            .filter_map(|suggestion| {
+                users.remove(&suggestion.user_id).map(|user| crate::models::FriendSuggestion {
+                    user,
+                    mutual_friend_count: suggestion.mutual_friend_count,
+                    shared_tags: suggestion.shared_tags,
+                    score: suggestion.score,
+                })
+            })
+            .collect())
+    }
+
     /// 친구 통계 조회
     async fn friend_stats(&self, ctx: &Context<'_>) -> Result<Option<crate::models::FriendStats>> {
         let user_id = ctx.data_opt::<String>()
@@ -239,19 +685,7 @@ impl QueryRoot {
         if let Ok(cache) = ctx.data::<crate::cache::FriendCache>() {
             // 친구 ID 목록을 캐시에 저장하기 위해 조회
             if is_friend {
-                let friend_ids: Vec<String> = sqlx::query_scalar(
-                    "SELECT CASE
-                        WHEN user_id = ? THEN friend_id
-                        ELSE user_id
-                    END AS friend_user_id
-                    FROM friendships
-                    WHERE user_id = ? OR friend_id = ?"
-                )
-                .bind(user_id)
-                .bind(user_id)
-                .bind(user_id)
-                .fetch_all(pool)
-                .await?;
+                let friend_ids = crate::models::Friendship::find_friend_ids(pool, user_id).await?;
 
                 let _ = cache.set_friend_ids(user_id, &friend_ids).await;
             }
@@ -273,107 +707,161 @@ impl QueryRoot {
         let pool = ctx.data::<SqlitePool>()?;
 
         // 캐시에서 친구 ID 목록 조회
-        let friend_ids: Vec<String> = if let Ok(cache) = ctx.data::<crate::cache::FriendCache>() {
-            if let Ok(Some(ids)) = cache.get_friend_ids(user_id).await {
-                ids
-            } else {
-                // 캐시 미스: DB에서 조회
-                let ids: Vec<String> = sqlx::query_scalar(
-                    "SELECT CASE
-                        WHEN user_id = ? THEN friend_id
-                        ELSE user_id
-                    END AS friend_user_id
-                    FROM friendships
-                    WHERE user_id = ? OR friend_id = ?"
-                )
-                .bind(user_id)
-                .bind(user_id)
-                .bind(user_id)
-                .fetch_all(pool)
-                .await?;
-
-                // 캐시에 저장
-                let _ = cache.set_friend_ids(user_id, &ids).await;
-                ids
-            }
-        } else {
-            // 캐시 사용 불가: DB에서 직접 조회
-            sqlx::query_scalar(
-                "SELECT CASE
-                    WHEN user_id = ? THEN friend_id
-                    ELSE user_id
-                END AS friend_user_id
-                FROM friendships
-                WHERE user_id = ? OR friend_id = ?"
-            )
-            .bind(user_id)
-            .bind(user_id)
-            .bind(user_id)
-            .fetch_all(pool)
-            .await?
-        };
+        let friend_ids = Friendship::load_friend_ids(ctx, user_id).await?;
 
         if friend_ids.is_empty() {
             return Ok(vec![]);
         }
 
-        // 친구들의 게시물 조회
+        // 친구들의 게시물 조회 (캐시가 오래된 경우에도 차단 관계인 사용자는 제외)
         let placeholders = friend_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
             "SELECT * FROM feed_posts
              WHERE author_id IN ({})
+             AND author_id NOT IN ({})
              ORDER BY created_at DESC
              LIMIT ? OFFSET ?",
-            placeholders
+            placeholders, BLOCKED_USER_IDS_SQL
         );
 
         let mut query_builder = sqlx::query_as::<_, FeedPost>(&query);
         for friend_id in &friend_ids {
             query_builder = query_builder.bind(friend_id);
         }
-        query_builder = query_builder.bind(limit).bind(offset);
+        query_builder = query_builder.bind(user_id).bind(user_id).bind(limit).bind(offset);
 
         let posts = query_builder.fetch_all(pool).await?;
 
         Ok(posts)
     }
 
-    /// 게시물 검색 (Elasticsearch)
+    /// 친구의 게시물 조회 (커서 기반 페이지네이션)
+    async fn friend_posts_connection(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<PageCursor, FeedPost>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let friend_ids = Friendship::load_friend_ids(ctx, user_id).await?;
+
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 20);
+            let has_previous_page = after.is_some();
+
+            if friend_ids.is_empty() {
+                return Ok::<_, Error>(into_connection(Vec::new(), limit, has_previous_page, |post: FeedPost| {
+                    (CursorKey::new(post.created_at, &post.id), post)
+                }));
+            }
+
+            let (cursor_at, cursor_id) = cursor_bounds(after);
+
+            let placeholders = friend_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            let query = format!(
+                "SELECT * FROM feed_posts
+                 WHERE author_id IN ({})
+                 AND author_id NOT IN ({})
+                 AND (? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))
+                 ORDER BY datetime(created_at) DESC, id DESC
+                 LIMIT ?",
+                placeholders, BLOCKED_USER_IDS_SQL
+            );
+
+            let mut query_builder = sqlx::query_as::<_, FeedPost>(&query);
+            for friend_id in &friend_ids {
+                query_builder = query_builder.bind(friend_id);
+            }
+            let posts = query_builder
+                .bind(user_id)
+                .bind(user_id)
+                .bind(cursor_at)
+                .bind(cursor_at)
+                .bind(cursor_at)
+                .bind(&cursor_id)
+                .bind((limit + 1) as i64)
+                .fetch_all(pool)
+                .await?;
+
+            Ok(into_connection(posts, limit, has_previous_page, |post| {
+                (CursorKey::new(post.created_at, &post.id), post)
+            }))
+        })
+        .await
+    }
+
+    /// 게시물 검색 (Elasticsearch 또는 SQLite FTS5, 필터 + 패싯 집계, 로그인 시 개인화 순위)
+    ///
+    /// category는 filter.categories의 단축 인자
     async fn search_posts(
         &self,
         ctx: &Context<'_>,
         query: String,
-        category: Option<String>,
+        category: Option<Category>,
+        filter: Option<SearchPostsFilter>,
         #[graphql(default = 0)] from: i64,
         #[graphql(default = 20)] size: i64,
     ) -> Result<SearchPostsResult> {
-        let search_service = ctx.data::<SearchService>()?;
+        let search_router = ctx.data::<SearchRouter>()?;
         let pool = ctx.data::<SqlitePool>()?;
 
-        let result = search_service
-            .search_posts(&query, category, from, size)
-            .await
-            .map_err(|e| format!("Search failed: {}", e))?;
+        // 차단 관계인 사용자의 포스트는 검색 결과에서 제외하고,
+        // 로그인한 경우 친구/선호 카테고리/최신 포스트 순위를 높임
+        let (excluded_author_ids, personalization) = match ctx.data_opt::<String>() {
+            Some(user_id) => {
+                let cache = ctx.data::<crate::cache::FriendCache>().ok();
+                (
+                    crate::models::FriendRequest::find_blocked_user_ids(pool, user_id).await?,
+                    Some(SearchPersonalization::load(pool, cache, user_id).await?),
+                )
+            }
+            None => (Vec::new(), None),
+        };
 
-        // PostDocument를 FeedPost로 변환
-        let mut posts = Vec::new();
-        for doc in result.posts {
-            if let Ok(post) = sqlx::query_as::<_, FeedPost>("SELECT * FROM feed_posts WHERE id = ?")
-                .bind(&doc.id)
-                .fetch_one(pool)
-                .await
-            {
-                posts.push(post);
+        let mut filter = filter.unwrap_or_default();
+        if let Some(category) = category {
+            let categories = filter.categories.get_or_insert_with(Vec::new);
+            if !categories.contains(&category) {
+                categories.push(category);
             }
         }
+        filter.validate()?;
 
-        Ok(SearchPostsResult {
-            posts,
-            total: result.total,
-        })
+        let result = search_router
+            .search_posts(&query, &filter, &excluded_author_ids, personalization.as_ref(), from, size)
+            .await
+            .map_err(|e| format!("Search failed: {}", e))?;
+
+        SearchPostsResult::hydrate(pool, result).await
+    }
+
+    /// 검색어 자동완성 (제목, 태그, 위치)
+    async fn search_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        prefix: String,
+        #[graphql(default = 8)] limit: i32,
+    ) -> Result<Vec<SearchSuggestion>> {
+        let search_router = ctx.data::<SearchRouter>()?;
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let excluded_author_ids = match ctx.data_opt::<String>() {
+            Some(user_id) => crate::models::FriendRequest::find_blocked_user_ids(pool, user_id).await?,
+            None => Vec::new(),
+        };
+
+        let suggestions = search_router
+            .search_suggestions(&prefix, &excluded_author_ids, limit.clamp(1, 20) as usize)
+            .await
+            .map_err(|e| format!("Search suggestions failed: {}", e))?;
+
+        Ok(suggestions)
     }
 
-    /// 친구 게시물 검색 (Elasticsearch + 캐시)
+    /// 친구 게시물 검색 (Elasticsearch 또는 SQLite FTS5 + 캐시)
     async fn search_friend_posts(
         &self,
         ctx: &Context<'_>,
@@ -382,81 +870,63 @@ impl QueryRoot {
         #[graphql(default = 20)] size: i64,
     ) -> Result<SearchPostsResult> {
         let user_id = ctx.data_opt::<String>().ok_or("Unauthorized")?;
-        let search_service = ctx.data::<SearchService>()?;
+        let search_router = ctx.data::<SearchRouter>()?;
         let pool = ctx.data::<SqlitePool>()?;
 
         // 캐시에서 친구 ID 목록 조회
-        let friend_ids: Vec<String> = if let Ok(cache) = ctx.data::<crate::cache::FriendCache>() {
-            if let Ok(Some(ids)) = cache.get_friend_ids(user_id).await {
-                ids
-            } else {
-                // 캐시 미스: DB에서 조회
-                let ids: Vec<String> = sqlx::query_scalar(
-                    "SELECT CASE
-                        WHEN user_id = ? THEN friend_id
-                        ELSE user_id
-                    END AS friend_user_id
-                    FROM friendships
-                    WHERE user_id = ? OR friend_id = ?"
-                )
-                .bind(user_id)
-                .bind(user_id)
-                .bind(user_id)
-                .fetch_all(pool)
-                .await?;
-
-                // 캐시에 저장
-                let _ = cache.set_friend_ids(user_id, &ids).await;
-                ids
-            }
-        } else {
-            // 캐시 사용 불가: DB에서 직접 조회
-            sqlx::query_scalar(
-                "SELECT CASE
-                    WHEN user_id = ? THEN friend_id
-                    ELSE user_id
-                END AS friend_user_id
-                FROM friendships
-                WHERE user_id = ? OR friend_id = ?"
-            )
-            .bind(user_id)
-            .bind(user_id)
-            .bind(user_id)
-            .fetch_all(pool)
-            .await?
-        };
+        let friend_ids = Friendship::load_friend_ids(ctx, user_id).await?;
 
         if friend_ids.is_empty() {
-            return Ok(SearchPostsResult {
-                posts: vec![],
-                total: 0,
-            });
+            return Ok(SearchPostsResult::default());
         }
 
-        let result = search_service
+        let result = search_router
             .search_friend_posts(&friend_ids, query.as_deref(), from, size)
             .await
             .map_err(|e| format!("Search failed: {}", e))?;
 
-        // PostDocument를 FeedPost로 변환
-        let mut posts = Vec::new();
-        for doc in result.posts {
-            if let Ok(post) = sqlx::query_as::<_, FeedPost>("SELECT * FROM feed_posts WHERE id = ?")
-                .bind(&doc.id)
-                .fetch_one(pool)
-                .await
-            {
-                posts.push(post);
-            }
+        SearchPostsResult::hydrate(pool, result).await
+    }
+
+    /// 검색 인덱싱 아웃박스 조회 (관리자 전용, 재시도 대기/DEAD 항목 확인)
+    async fn search_outbox(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<OutboxStatus>,
+        #[graphql(default = 50)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+    ) -> Result<Vec<OutboxEntry>> {
+        let user_id = ctx.data_opt::<String>().ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
         }
 
-        Ok(SearchPostsResult {
-            posts,
-            total: result.total,
-        })
+        let pool = ctx.data::<SqlitePool>()?;
+        let entries = OutboxEntry::list(pool, status, limit.clamp(1, 200), offset.max(0)).await?;
+
+        Ok(entries)
+    }
+
+    /// 이미지 처리 작업 조회 (관리자 전용, 재시도 대기/DEAD 작업 확인)
+    async fn image_jobs(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<ImageJobStatus>,
+        #[graphql(default = 50)] limit: i64,
+        #[graphql(default = 0)] offset: i64,
+    ) -> Result<Vec<ImageJob>> {
+        let user_id = ctx.data_opt::<String>().ok_or("Unauthorized")?;
+        if !is_admin(user_id) {
+            return Err("Forbidden".into());
+        }
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let jobs = ImageJob::list(pool, status, limit.clamp(1, 200), offset.max(0)).await?;
+
+        Ok(jobs)
     }
 
-    /// 사용자 검색 (이름 또는 이메일로 검색)
+    /// 사용자 검색 (이름, 닉네임, 초성으로 검색하고 친구와 친구의 친구를 먼저 표시)
     async fn search_users(
         &self,
         ctx: &Context<'_>,
@@ -464,49 +934,133 @@ impl QueryRoot {
         #[graphql(default = 20)] limit: i64,
         #[graphql(default = 0)] offset: i64,
     ) -> Result<Vec<User>> {
-        let current_user_id = ctx.data_opt::<String>();
+        let users = search_ranked_users(ctx, &query, offset.max(0), limit.clamp(1, 100)).await?;
+        Ok(users.into_iter().flatten().collect())
+    }
+
+    /// 사용자 검색 (커서 기반 페이지네이션, searchUsers와 같은 순위)
+    ///
+    /// 커서는 순위상의 위치(offset)이므로 페이지 사이에 가입, 프로필 수정, 친구 관계 변경으로
+    /// 순위가 바뀌면 항목이 중복되거나 빠질 수 있음
+    async fn search_users_connection(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<RankCursor, User>> {
+        connection::query(after, None, first, None, |after: Option<RankCursor>, _, first, _| async move {
+            let limit = page_size(first, 20);
+            let offset = after.map_or(0, |cursor| cursor.0);
+
+            let users = search_ranked_users(ctx, &query, offset as i64, (limit + 1) as i64).await?;
+
+            Ok::<_, Error>(into_ranked_connection(users, offset, limit))
+        })
+        .await
+    }
+
+    /// 내 알림 목록 (최신순, 커서 기반 페이지네이션)
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+        #[graphql(default = false)] unread_only: bool,
+    ) -> Result<Connection<PageCursor, crate::models::Notification>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
         let pool = ctx.data::<SqlitePool>()?;
 
-        let search_pattern = format!("%{}%", query);
+        connection::query(after, None, first, None, |after: Option<PageCursor>, _, first, _| async move {
+            let limit = page_size(first, 20);
+            let has_previous_page = after.is_some();
+            let (cursor_at, cursor_id) = cursor_bounds(after);
 
-        // 현재 로그인한 사용자는 검색 결과에서 제외
-        let users = if let Some(user_id) = current_user_id {
-            sqlx::query_as::<_, User>(
-                "SELECT * FROM users
-                 WHERE (name LIKE ? OR email LIKE ?)
-                 AND id != ?
-                 ORDER BY name ASC
-                 LIMIT ? OFFSET ?"
+            let notifications = sqlx::query_as::<_, crate::models::Notification>(
+                "SELECT * FROM notifications
+                 WHERE recipient_id = ? AND (? = 0 OR is_read = 0)
+                 AND (? IS NULL OR datetime(created_at) < datetime(?) OR (datetime(created_at) = datetime(?) AND id < ?))
+                 ORDER BY datetime(created_at) DESC, id DESC
+                 LIMIT ?"
             )
-            .bind(&search_pattern)
-            .bind(&search_pattern)
             .bind(user_id)
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(pool)
-            .await?
-        } else {
-            // 로그인하지 않은 경우에도 검색 가능
-            sqlx::query_as::<_, User>(
-                "SELECT * FROM users
-                 WHERE name LIKE ? OR email LIKE ?
-                 ORDER BY name ASC
-                 LIMIT ? OFFSET ?"
-            )
-            .bind(&search_pattern)
-            .bind(&search_pattern)
-            .bind(limit)
-            .bind(offset)
+            .bind(unread_only)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(cursor_at)
+            .bind(&cursor_id)
+            .bind((limit + 1) as i64)
             .fetch_all(pool)
-            .await?
-        };
+            .await?;
 
-        Ok(users)
+            Ok::<_, Error>(into_connection(notifications, limit, has_previous_page, |notification| {
+                (CursorKey::new(notification.created_at, &notification.id), notification)
+            }))
+        })
+        .await
+    }
+
+    /// 읽지 않은 알림 수
+    async fn unread_notification_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?;
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        Ok(crate::models::Notification::unread_count(pool, user_id).await?)
     }
 }
 
-#[derive(SimpleObject)]
+#[derive(SimpleObject, Default)]
 pub struct SearchPostsResult {
     pub posts: Vec<FeedPost>,
+    /// 포스트별 관련도 점수와 하이라이트 (posts와 같은 순서)
+    pub hits: Vec<SearchPostHit>,
     pub total: i64,
+    /// 필터 칩용 집계 (카테고리별 수, 상위 태그, 별점 분포)
+    pub facets: SearchFacets,
+}
+
+/// 검색 결과 항목
+#[derive(SimpleObject)]
+pub struct SearchPostHit {
+    pub post: FeedPost,
+    /// 관련도 점수 (검색어 없이 최신순 조회한 경우 null)
+    pub score: Option<f64>,
+    /// 제목 하이라이트 조각 (`<em>` 태그 포함, 나머지는 HTML 이스케이프)
+    pub title_highlights: Vec<String>,
+    /// 본문 하이라이트 조각
+    pub content_highlights: Vec<String>,
+}
+
+impl SearchPostsResult {
+    /// Elasticsearch 결과를 SQLite 포스트로 채움 (한 번의 쿼리, 랭킹 순서 유지)
+    async fn hydrate(pool: &SqlitePool, result: crate::search::service::SearchResult) -> Result<Self> {
+        let ids: Vec<String> = result.hits.iter().map(|hit| hit.document.id.clone()).collect();
+        let mut posts_by_id = load_posts_by_ids(pool, &ids).await?;
+
+        // 인덱스에만 남아있는(삭제된) 포스트는 제외
+        let hits: Vec<SearchPostHit> = result
+            .hits
+            .into_iter()
+            .filter_map(|mut hit| {
+                let post = posts_by_id.remove(&hit.document.id)?;
+                Some(SearchPostHit {
+                    post,
+                    score: hit.score,
+                    title_highlights: hit.highlights.remove("title").unwrap_or_default(),
+                    content_highlights: hit.highlights.remove("content").unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            posts: hits.iter().map(|hit| hit.post.clone()).collect(),
+            hits,
+            total: result.total,
+            facets: result.facets,
+        })
+    }
 }
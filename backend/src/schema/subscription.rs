@@ -0,0 +1,79 @@
+use async_graphql::*;
+use futures_util::{future, Stream, StreamExt};
+
+use crate::events::{AppEvent, EventBus};
+use crate::models::{Comment, FriendRequest, PostImageStatus, PostLikeCount};
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// 포스트에 새 댓글이 작성될 때
+    async fn comment_added(
+        &self,
+        ctx: &Context<'_>,
+        post_id: String,
+    ) -> Result<impl Stream<Item = Comment>> {
+        let bus = ctx.data::<EventBus>()?;
+
+        Ok(bus.subscribe().filter_map(move |event| {
+            future::ready(match event {
+                AppEvent::CommentAdded(comment) if comment.post_id == post_id => Some(comment),
+                _ => None,
+            })
+        }))
+    }
+
+    /// 포스트의 좋아요 수가 변경될 때
+    async fn post_like_count_changed(
+        &self,
+        ctx: &Context<'_>,
+        post_id: String,
+    ) -> Result<impl Stream<Item = PostLikeCount>> {
+        let bus = ctx.data::<EventBus>()?;
+
+        Ok(bus.subscribe().filter_map(move |event| {
+            future::ready(match event {
+                AppEvent::PostLikeCountChanged(count) if count.post_id == post_id => Some(count),
+                _ => None,
+            })
+        }))
+    }
+
+    /// 포스트 이미지 처리가 끝났을 때 (READY 또는 FAILED)
+    async fn post_image_status_changed(
+        &self,
+        ctx: &Context<'_>,
+        post_id: String,
+    ) -> Result<impl Stream<Item = PostImageStatus>> {
+        let bus = ctx.data::<EventBus>()?;
+
+        Ok(bus.subscribe().filter_map(move |event| {
+            future::ready(match event {
+                AppEvent::PostImageStatusChanged(status) if status.post_id == post_id => Some(status),
+                _ => None,
+            })
+        }))
+    }
+
+    /// 현재 사용자에게 친구 요청이 도착할 때
+    async fn friend_request_received(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = FriendRequest>> {
+        let user_id = ctx.data_opt::<String>()
+            .ok_or("Unauthorized")?
+            .clone();
+
+        let bus = ctx.data::<EventBus>()?;
+
+        Ok(bus.subscribe().filter_map(move |event| {
+            future::ready(match event {
+                AppEvent::FriendRequestReceived(request) if request.addressee_id == user_id => {
+                    Some(request)
+                }
+                _ => None,
+            })
+        }))
+    }
+}
@@ -0,0 +1,320 @@
+use std::error::Error;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::search::facets::SearchPostsFilter;
+use crate::search::personalization::SearchPersonalization;
+use crate::search::service::SearchResult;
+use crate::search::sqlite::SqliteSearch;
+use crate::search::suggest::SearchSuggestion;
+use crate::search::users::UserSearchService;
+use crate::search::SearchService;
+
+/// 자동 전환 모드에서 Elasticsearch 상태를 확인하는 주기
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub type SearchError = Box<dyn Error + Send + Sync>;
+
+/// 게시물 검색 백엔드 (Elasticsearch, SQLite FTS5)
+pub trait SearchBackend {
+    fn search_posts(
+        &self,
+        query: &str,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
+        from: i64,
+        size: i64,
+    ) -> impl Future<Output = Result<SearchResult, SearchError>> + Send;
+
+    fn search_friend_posts(
+        &self,
+        friend_ids: &[String],
+        query: Option<&str>,
+        from: i64,
+        size: i64,
+    ) -> impl Future<Output = Result<SearchResult, SearchError>> + Send;
+
+    fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<SearchSuggestion>, SearchError>> + Send;
+}
+
+impl SearchBackend for SearchService {
+    async fn search_posts(
+        &self,
+        query: &str,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        SearchService::search_posts(self, query, filter, excluded_author_ids, personalization, from, size)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+
+    async fn search_friend_posts(
+        &self,
+        friend_ids: &[String],
+        query: Option<&str>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        SearchService::search_friend_posts(self, friend_ids, query, from, size)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+
+    async fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>, SearchError> {
+        SearchService::search_suggestions(self, prefix, excluded_author_ids, limit)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+}
+
+impl SearchBackend for SqliteSearch {
+    async fn search_posts(
+        &self,
+        query: &str,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        Ok(SqliteSearch::search_posts(self, query, filter, excluded_author_ids, personalization, from, size).await?)
+    }
+
+    async fn search_friend_posts(
+        &self,
+        friend_ids: &[String],
+        query: Option<&str>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        Ok(SqliteSearch::search_friend_posts(self, friend_ids, query, from, size).await?)
+    }
+
+    async fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>, SearchError> {
+        Ok(SqliteSearch::search_suggestions(self, prefix, excluded_author_ids, limit).await?)
+    }
+}
+
+/// 검색 백엔드 선택 (SEARCH_BACKEND 환경 변수)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchBackendKind {
+    Elasticsearch,
+    Sqlite,
+    /// Elasticsearch 우선, 헬스 체크나 요청이 실패하면 SQLite FTS5로 전환
+    Auto,
+}
+
+impl SearchBackendKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "elasticsearch" | "es" => Some(Self::Elasticsearch),
+            "sqlite" | "fts" => Some(Self::Sqlite),
+            "auto" | "" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// 설정과 Elasticsearch 상태에 따라 검색 백엔드 선택
+#[derive(Clone)]
+pub struct SearchRouter {
+    kind: SearchBackendKind,
+    elasticsearch: SearchService,
+    users: UserSearchService,
+    sqlite: SqliteSearch,
+    elasticsearch_available: Arc<AtomicBool>,
+}
+
+impl SearchRouter {
+    pub fn new(
+        kind: SearchBackendKind,
+        elasticsearch: SearchService,
+        users: UserSearchService,
+        sqlite: SqliteSearch,
+    ) -> Self {
+        Self {
+            kind,
+            elasticsearch,
+            users,
+            sqlite,
+            elasticsearch_available: Arc::new(AtomicBool::new(kind != SearchBackendKind::Sqlite)),
+        }
+    }
+
+    pub fn kind(&self) -> SearchBackendKind {
+        self.kind
+    }
+
+    /// 자동 전환 모드이면 주기적으로 Elasticsearch 상태 확인 (복구되면 다시 사용)
+    pub fn start_health_check(&self) {
+        if self.kind != SearchBackendKind::Auto {
+            return;
+        }
+
+        let router = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let healthy = router.elasticsearch.health_check().await.is_ok();
+                let was_available = router.elasticsearch_available.swap(healthy, Ordering::SeqCst);
+
+                if was_available && !healthy {
+                    log::warn!("Elasticsearch is unavailable, searching with SQLite FTS5");
+                } else if !was_available && healthy {
+                    log::info!("Elasticsearch is available again");
+                }
+
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    /// 지금 사용할 수 있는 Elasticsearch (SQLite 모드이거나 장애 중이면 None)
+    pub fn elasticsearch(&self) -> Option<&SearchService> {
+        match self.kind {
+            SearchBackendKind::Sqlite => None,
+            SearchBackendKind::Elasticsearch => Some(&self.elasticsearch),
+            SearchBackendKind::Auto => self
+                .elasticsearch_available
+                .load(Ordering::SeqCst)
+                .then_some(&self.elasticsearch),
+        }
+    }
+
+    /// 지금 사용할 수 있는 사용자 검색 인덱스 (게시물 인덱스와 같은 클러스터, alias가 준비된 경우만)
+    pub fn users(&self) -> Option<&UserSearchService> {
+        self.elasticsearch()
+            .filter(|_| self.users.is_ready())
+            .map(|_| &self.users)
+    }
+
+    /// 사용자 검색 (Elasticsearch를 쓸 수 없으면 SQLite에서 순위 계산), 순위대로 ID 반환
+    ///
+    /// 사용자 인덱스 오류는 포스트 검색과 무관하므로 전환하지 않고 이번 요청만 SQLite로 처리
+    pub async fn search_users(
+        &self,
+        query: &str,
+        friend_ids: &[String],
+        friend_of_friend_ids: &[String],
+        excluded_ids: &[String],
+        from: i64,
+        size: i64,
+    ) -> Result<Vec<String>, SearchError> {
+        if let Some(users) = self.users() {
+            let result = users
+                .search_user_ids(query, friend_ids, friend_of_friend_ids, excluded_ids, from, size)
+                .await
+                .map_err(|e| e.to_string());
+            match result {
+                Ok(ids) => return Ok(ids),
+                Err(e) if self.kind == SearchBackendKind::Auto => {
+                    log::warn!("Elasticsearch user search failed, searching users with SQLite: {}", e);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(self
+            .sqlite
+            .search_users(query, friend_ids, friend_of_friend_ids, excluded_ids, from, size)
+            .await?)
+    }
+
+    /// 요청 실패 시 다음 헬스 체크까지 SQLite로 전환 (자동 모드가 아니면 오류 그대로 반환)
+    fn fall_back(&self, error: SearchError) -> Result<(), SearchError> {
+        if self.kind != SearchBackendKind::Auto {
+            return Err(error);
+        }
+
+        log::warn!("Elasticsearch search failed, falling back to SQLite FTS5: {}", error);
+        self.elasticsearch_available.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl SearchBackend for SearchRouter {
+    async fn search_posts(
+        &self,
+        query: &str,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        if let Some(elasticsearch) = self.elasticsearch() {
+            let result =
+                SearchBackend::search_posts(elasticsearch, query, filter, excluded_author_ids, personalization, from, size)
+                    .await;
+            match result {
+                Ok(result) => return Ok(result),
+                Err(e) => self.fall_back(e)?,
+            }
+        }
+        SearchBackend::search_posts(&self.sqlite, query, filter, excluded_author_ids, personalization, from, size).await
+    }
+
+    async fn search_friend_posts(
+        &self,
+        friend_ids: &[String],
+        query: Option<&str>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, SearchError> {
+        if let Some(elasticsearch) = self.elasticsearch() {
+            match SearchBackend::search_friend_posts(elasticsearch, friend_ids, query, from, size).await {
+                Ok(result) => return Ok(result),
+                Err(e) => self.fall_back(e)?,
+            }
+        }
+        SearchBackend::search_friend_posts(&self.sqlite, friend_ids, query, from, size).await
+    }
+
+    async fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>, SearchError> {
+        if let Some(elasticsearch) = self.elasticsearch() {
+            match SearchBackend::search_suggestions(elasticsearch, prefix, excluded_author_ids, limit).await {
+                Ok(suggestions) => return Ok(suggestions),
+                Err(e) => self.fall_back(e)?,
+            }
+        }
+        SearchBackend::search_suggestions(&self.sqlite, prefix, excluded_author_ids, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_kind() {
+        assert_eq!(SearchBackendKind::parse("Elasticsearch"), Some(SearchBackendKind::Elasticsearch));
+        assert_eq!(SearchBackendKind::parse(" sqlite "), Some(SearchBackendKind::Sqlite));
+        assert_eq!(SearchBackendKind::parse(""), Some(SearchBackendKind::Auto));
+        assert_eq!(SearchBackendKind::parse("solr"), None);
+    }
+}
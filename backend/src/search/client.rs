@@ -1,11 +1,15 @@
 use elasticsearch::{
     auth::Credentials,
     http::transport::{CloudConnectionPool, SingleNodeConnectionPool, TransportBuilder},
+    indices::{IndicesCreateParts, IndicesDeleteParts, IndicesGetAliasParts, IndicesGetParts, IndicesRefreshParts},
     Elasticsearch,
 };
+use serde_json::{json, Value};
 use std::error::Error;
 use std::time::Duration;
 
+use crate::search::reindex::{next_index_version, versioned_index_name};
+
 #[derive(Clone)]
 pub struct ElasticsearchClient {
     client: Elasticsearch,
@@ -114,10 +118,145 @@ impl ElasticsearchClient {
         let status = response.status_code();
 
         if status.is_success() {
-            log::info!("Elasticsearch cluster is healthy");
+            log::debug!("Elasticsearch cluster is healthy");
             Ok(())
         } else {
             Err(format!("Elasticsearch cluster health check failed: {}", status).into())
         }
     }
+
+    /// 설정/매핑으로 인덱스 생성
+    pub async fn create_index(&self, index_name: &str, body: Value) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .client
+            .indices()
+            .create(IndicesCreateParts::Index(index_name))
+            .body(body)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to create index {}: {}", index_name, text).into());
+        }
+
+        log::info!("Index {} created successfully", index_name);
+        Ok(())
+    }
+
+    /// 읽기/쓰기 alias가 연결된 인덱스 목록
+    pub async fn alias_indices(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let alias_name = self.index_alias();
+        let client = &self.client;
+
+        let response = client
+            .indices()
+            .get_alias(IndicesGetAliasParts::Name(&[alias_name]))
+            .send()
+            .await?;
+
+        if response.status_code().as_u16() == 404 {
+            return Ok(vec![]);
+        }
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to get alias {}: {}", alias_name, text).into());
+        }
+
+        let body: Value = response.json().await?;
+        Ok(body.as_object().map(|indices| indices.keys().cloned().collect()).unwrap_or_default())
+    }
+
+    /// 다음 버전 인덱스 이름 (`{index}_v{n}`, 남아있는 버전 인덱스 중 최대 + 1)
+    pub async fn next_index_name(&self) -> Result<String, Box<dyn Error>> {
+        let base = self.index_name();
+        let client = &self.client;
+        let pattern = format!("{}_v*", base);
+
+        let response = client
+            .indices()
+            .get(IndicesGetParts::Index(&[pattern.as_str()]))
+            .allow_no_indices(true)
+            .ignore_unavailable(true)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to list indices: {}", text).into());
+        }
+
+        let body: Value = response.json().await?;
+        let existing: Vec<&str> = body
+            .as_object()
+            .map(|indices| indices.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+
+        Ok(versioned_index_name(base, next_index_version(base, &existing)))
+    }
+
+    /// alias를 새 인덱스로 원자적으로 이동, 이전에 연결되어 있던 인덱스 목록 반환
+    pub async fn swap_alias(&self, new_index: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let alias_name = self.index_alias();
+        let client = &self.client;
+        let previous = self.alias_indices().await?;
+
+        let mut actions: Vec<Value> = previous
+            .iter()
+            .filter(|index| index.as_str() != new_index)
+            .map(|index| json!({ "remove": { "index": index, "alias": alias_name } }))
+            .collect();
+        actions.push(json!({
+            "add": { "index": new_index, "alias": alias_name, "is_write_index": true }
+        }));
+
+        let response = client
+            .indices()
+            .update_aliases()
+            .body(json!({ "actions": actions }))
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to move alias {}: {}", alias_name, text).into());
+        }
+
+        log::info!("Alias {} now points to {}", alias_name, new_index);
+        Ok(previous.into_iter().filter(|index| index != new_index).collect())
+    }
+
+    /// 새로 쓴 문서가 검색되도록 인덱스 refresh
+    pub async fn refresh_index(&self, index_name: &str) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .client
+            .indices()
+            .refresh(IndicesRefreshParts::Index(&[index_name]))
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to refresh index {}: {}", index_name, text).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete_index(&self, index_name: &str) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .client
+            .indices()
+            .delete(IndicesDeleteParts::Index(&[index_name]))
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Failed to delete index {}: {}", index_name, text).into());
+        }
+
+        log::info!("Index {} deleted", index_name);
+        Ok(())
+    }
 }
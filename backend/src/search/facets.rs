@@ -0,0 +1,402 @@
+use async_graphql::{InputObject, SimpleObject};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::models::Category;
+
+/// 태그 집계 버킷 수
+const TOP_TAGS_SIZE: usize = 20;
+
+/// 인덱스에 저장되는 카테고리 키워드
+pub fn category_keyword(category: Category) -> &'static str {
+    match category {
+        Category::Korean => "KOREAN",
+        Category::Western => "WESTERN",
+        Category::Chinese => "CHINESE",
+        Category::Japanese => "JAPANESE",
+        Category::Cafe => "CAFE",
+        Category::Dessert => "DESSERT",
+    }
+}
+
+pub fn category_from_keyword(keyword: &str) -> Option<Category> {
+    match keyword {
+        "KOREAN" => Some(Category::Korean),
+        "WESTERN" => Some(Category::Western),
+        "CHINESE" => Some(Category::Chinese),
+        "JAPANESE" => Some(Category::Japanese),
+        "CAFE" => Some(Category::Cafe),
+        "DESSERT" => Some(Category::Dessert),
+        _ => None,
+    }
+}
+
+/// 게시물 검색 필터
+///
+/// 카테고리는 하나라도 일치(OR), 태그는 모두 포함(AND)
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct SearchPostsFilter {
+    pub categories: Option<Vec<Category>>,
+    pub tags: Option<Vec<String>>,
+    pub min_rating: Option<f64>,
+    pub max_rating: Option<f64>,
+    /// 작성 시각 하한 (RFC 3339)
+    pub created_after: Option<String>,
+    /// 작성 시각 상한 (RFC 3339)
+    pub created_before: Option<String>,
+    pub author_id: Option<String>,
+}
+
+impl SearchPostsFilter {
+    pub fn validate(&self) -> Result<(), String> {
+        for rating in [self.min_rating, self.max_rating].into_iter().flatten() {
+            if !(0.0..=5.0).contains(&rating) {
+                return Err("Rating filter must be between 0 and 5".to_string());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_rating, self.max_rating) {
+            if min > max {
+                return Err("minRating must not be greater than maxRating".to_string());
+            }
+        }
+
+        for date in [&self.created_after, &self.created_before].into_iter().flatten() {
+            DateTime::parse_from_rfc3339(date)
+                .map_err(|_| format!("Invalid date (expected RFC 3339): {}", date))?;
+        }
+
+        Ok(())
+    }
+
+    /// 집계 대상과 무관한 필터 (검색 쿼리에 적용)
+    pub fn base_clauses(&self) -> Vec<Value> {
+        let mut clauses = Vec::new();
+
+        if self.created_after.is_some() || self.created_before.is_some() {
+            let mut range = serde_json::Map::new();
+            if let Some(after) = &self.created_after {
+                range.insert("gte".to_string(), json!(after));
+            }
+            if let Some(before) = &self.created_before {
+                range.insert("lte".to_string(), json!(before));
+            }
+            clauses.push(json!({ "range": { "created_at": range } }));
+        }
+
+        if let Some(author_id) = &self.author_id {
+            clauses.push(json!({ "term": { "author_id": author_id } }));
+        }
+
+        clauses
+    }
+
+    fn category_clause(&self) -> Option<Value> {
+        let categories = self.categories.as_ref().filter(|c| !c.is_empty())?;
+        let keywords: Vec<&str> = categories.iter().map(|c| category_keyword(*c)).collect();
+        Some(json!({ "terms": { "category": keywords } }))
+    }
+
+    fn tag_clauses(&self) -> Vec<Value> {
+        self.tags
+            .iter()
+            .flatten()
+            .map(|tag| json!({ "term": { "tags": tag } }))
+            .collect()
+    }
+
+    fn rating_clause(&self) -> Option<Value> {
+        if self.min_rating.is_none() && self.max_rating.is_none() {
+            return None;
+        }
+
+        let mut range = serde_json::Map::new();
+        if let Some(min) = self.min_rating {
+            range.insert("gte".to_string(), json!(min));
+        }
+        if let Some(max) = self.max_rating {
+            range.insert("lte".to_string(), json!(max));
+        }
+        Some(json!({ "range": { "rating": range } }))
+    }
+
+    /// 패싯 필터 (post_filter로 적용하여 집계 수에는 영향을 주지 않음)
+    pub fn post_filter(&self) -> Value {
+        let clauses: Vec<Value> = self
+            .category_clause()
+            .into_iter()
+            .chain(self.tag_clauses())
+            .chain(self.rating_clause())
+            .collect();
+
+        json!({ "bool": { "filter": clauses } })
+    }
+
+    fn matches_category(&self, category: Category) -> bool {
+        match self.categories.as_ref().filter(|c| !c.is_empty()) {
+            Some(categories) => categories.contains(&category),
+            None => true,
+        }
+    }
+
+    fn matches_tags(&self, tags: &[String]) -> bool {
+        self.tags.iter().flatten().all(|tag| tags.contains(tag))
+    }
+
+    fn matches_rating(&self, rating: f64) -> bool {
+        self.min_rating.is_none_or(|min| rating >= min) && self.max_rating.is_none_or(|max| rating <= max)
+    }
+
+    /// 패싯 필터 일치 여부 (Elasticsearch 없이 검색할 때 `post_filter` 대신 사용)
+    pub fn matches_facets(&self, category: Category, tags: &[String], rating: f64) -> bool {
+        self.matches_category(category) && self.matches_tags(tags) && self.matches_rating(rating)
+    }
+
+    /// 패싯 집계 (각 집계는 자기 자신을 제외한 나머지 패싯 필터만 적용)
+    pub fn aggregations(&self) -> Value {
+        let filtered = |clauses: Vec<Value>| json!({ "bool": { "filter": clauses } });
+
+        json!({
+            "categories": {
+                "filter": filtered(self.tag_clauses().into_iter().chain(self.rating_clause()).collect()),
+                "aggs": {
+                    "values": { "terms": { "field": "category", "size": 10 } }
+                }
+            },
+            "tags": {
+                "filter": filtered(self.category_clause().into_iter().chain(self.rating_clause()).collect()),
+                "aggs": {
+                    "values": { "terms": { "field": "tags", "size": TOP_TAGS_SIZE } }
+                }
+            },
+            "ratings": {
+                "filter": filtered(self.category_clause().into_iter().chain(self.tag_clauses()).collect()),
+                "aggs": {
+                    "values": {
+                        "histogram": {
+                            "field": "rating",
+                            "interval": 1,
+                            "min_doc_count": 0,
+                            "extended_bounds": { "min": 0, "max": 5 }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SimpleObject)]
+pub struct CategoryFacet {
+    pub category: Category,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SimpleObject)]
+pub struct TagFacet {
+    pub tag: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SimpleObject)]
+pub struct RatingFacet {
+    /// 구간 하한 (4.0이면 4.0 이상 5.0 미만, 5.0은 만점)
+    pub rating: f64,
+    pub count: i64,
+}
+
+/// 검색 결과 패싯 (필터 칩 표시용)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, SimpleObject)]
+pub struct SearchFacets {
+    pub categories: Vec<CategoryFacet>,
+    /// 상위 태그
+    pub tags: Vec<TagFacet>,
+    /// 별점 히스토그램 (0~5, 1점 단위)
+    pub ratings: Vec<RatingFacet>,
+}
+
+impl SearchFacets {
+    /// 검색 응답의 aggregations에서 패싯 추출
+    pub fn from_aggregations(aggregations: &Value) -> Self {
+        let buckets = |name: &str| -> Vec<(Value, i64)> {
+            aggregations[name]["values"]["buckets"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|bucket| (bucket["key"].clone(), bucket["doc_count"].as_i64().unwrap_or(0)))
+                .collect()
+        };
+
+        Self {
+            categories: buckets("categories")
+                .into_iter()
+                .filter_map(|(key, count)| {
+                    let category = category_from_keyword(key.as_str()?)?;
+                    Some(CategoryFacet { category, count })
+                })
+                .collect(),
+            tags: buckets("tags")
+                .into_iter()
+                .filter_map(|(key, count)| {
+                    Some(TagFacet { tag: key.as_str()?.to_string(), count })
+                })
+                .collect(),
+            ratings: buckets("ratings")
+                .into_iter()
+                .filter_map(|(key, count)| Some(RatingFacet { rating: key.as_f64()?, count }))
+                .collect(),
+        }
+    }
+}
+
+impl SearchFacets {
+    /// 후보 문서(카테고리, 태그, 별점)에서 직접 패싯 계산 (`aggregations`와 같은 규칙)
+    pub fn count<'a, I>(candidates: I, filter: &SearchPostsFilter) -> Self
+    where
+        I: IntoIterator<Item = (Category, &'a [String], f64)>,
+    {
+        let mut categories: Vec<CategoryFacet> = Vec::new();
+        let mut tags: Vec<TagFacet> = Vec::new();
+        let mut ratings: Vec<RatingFacet> = (0..=5)
+            .map(|rating| RatingFacet { rating: rating as f64, count: 0 })
+            .collect();
+
+        for (category, post_tags, rating) in candidates {
+            if filter.matches_tags(post_tags) && filter.matches_rating(rating) {
+                match categories.iter_mut().find(|facet| facet.category == category) {
+                    Some(facet) => facet.count += 1,
+                    None => categories.push(CategoryFacet { category, count: 1 }),
+                }
+            }
+
+            if filter.matches_category(category) && filter.matches_rating(rating) {
+                for tag in post_tags {
+                    match tags.iter_mut().find(|facet| &facet.tag == tag) {
+                        Some(facet) => facet.count += 1,
+                        None => tags.push(TagFacet { tag: tag.clone(), count: 1 }),
+                    }
+                }
+            }
+
+            if filter.matches_category(category) && filter.matches_tags(post_tags) {
+                if let Some(facet) = ratings.get_mut(rating.floor().clamp(0.0, 5.0) as usize) {
+                    facet.count += 1;
+                }
+            }
+        }
+
+        // terms 집계와 같은 순서 (개수 내림차순, 같으면 키 오름차순)
+        categories.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| category_keyword(a.category).cmp(category_keyword(b.category)))
+        });
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        tags.truncate(TOP_TAGS_SIZE);
+
+        Self { categories, tags, ratings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let filter = SearchPostsFilter {
+            min_rating: Some(4.0),
+            max_rating: Some(3.0),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_err());
+
+        let filter = SearchPostsFilter {
+            created_after: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_err());
+
+        let filter = SearchPostsFilter {
+            min_rating: Some(3.5),
+            created_after: Some("2025-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_ok());
+    }
+
+    #[test]
+    fn test_aggregations_exclude_own_facet() {
+        let filter = SearchPostsFilter {
+            categories: Some(vec![Category::Japanese, Category::Cafe]),
+            tags: Some(vec!["라멘".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            filter.post_filter()["bool"]["filter"][0],
+            json!({ "terms": { "category": ["JAPANESE", "CAFE"] } })
+        );
+
+        let aggs = filter.aggregations();
+        // 카테고리 집계에는 태그 필터만, 태그 집계에는 카테고리 필터만 적용
+        assert_eq!(
+            aggs["categories"]["filter"]["bool"]["filter"],
+            json!([{ "term": { "tags": "라멘" } }])
+        );
+        assert_eq!(
+            aggs["tags"]["filter"]["bool"]["filter"],
+            json!([{ "terms": { "category": ["JAPANESE", "CAFE"] } }])
+        );
+    }
+
+    #[test]
+    fn test_from_aggregations() {
+        let aggregations = json!({
+            "categories": { "values": { "buckets": [
+                { "key": "JAPANESE", "doc_count": 3 },
+                { "key": "UNKNOWN", "doc_count": 1 }
+            ] } },
+            "tags": { "values": { "buckets": [{ "key": "라멘", "doc_count": 2 }] } },
+            "ratings": { "values": { "buckets": [{ "key": 4.0, "doc_count": 5 }] } }
+        });
+
+        let facets = SearchFacets::from_aggregations(&aggregations);
+        assert_eq!(facets.categories, vec![CategoryFacet { category: Category::Japanese, count: 3 }]);
+        assert_eq!(facets.tags, vec![TagFacet { tag: "라멘".to_string(), count: 2 }]);
+        assert_eq!(facets.ratings, vec![RatingFacet { rating: 4.0, count: 5 }]);
+        assert_eq!(SearchFacets::from_aggregations(&Value::Null), SearchFacets::default());
+    }
+
+    #[test]
+    fn test_count_excludes_own_facet() {
+        let ramen = vec!["라멘".to_string()];
+        let none: Vec<String> = vec![];
+        let candidates = vec![
+            (Category::Japanese, ramen.as_slice(), 4.5),
+            (Category::Japanese, none.as_slice(), 3.0),
+            (Category::Korean, ramen.as_slice(), 5.0),
+        ];
+        let filter = SearchPostsFilter {
+            categories: Some(vec![Category::Japanese]),
+            tags: Some(ramen.clone()),
+            ..Default::default()
+        };
+
+        assert!(filter.matches_facets(Category::Japanese, &ramen, 4.5));
+        assert!(!filter.matches_facets(Category::Japanese, &none, 3.0));
+
+        let facets = SearchFacets::count(candidates, &filter);
+        // 카테고리는 태그 필터만 적용, 태그는 카테고리 필터만 적용
+        assert_eq!(
+            facets.categories,
+            vec![
+                CategoryFacet { category: Category::Japanese, count: 1 },
+                CategoryFacet { category: Category::Korean, count: 1 },
+            ]
+        );
+        assert_eq!(facets.tags, vec![TagFacet { tag: "라멘".to_string(), count: 1 }]);
+        assert_eq!(facets.ratings.len(), 6);
+        assert_eq!(facets.ratings[4], RatingFacet { rating: 4.0, count: 1 });
+    }
+}
@@ -0,0 +1,284 @@
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+
+use crate::models::{FeedPost, User};
+use crate::search::outbox::{OutboxEntry, OutboxKind};
+use crate::search::reindex::{reindex_posts, ReindexReport};
+use crate::search::{SearchService, UserSearchService};
+
+/// 연속된 변경(좋아요 연타 등)을 모으는 시간
+const DEBOUNCE: Duration = Duration::from_secs(1);
+/// 깨우는 신호가 없어도 아웃박스를 확인하는 주기 (재시도, 다른 인스턴스의 기록)
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 한 번에 처리할 최대 항목 수
+const MAX_BATCH: i64 = 500;
+
+/// 검색 인덱싱 아웃박스 워커
+///
+/// 포스트 변경 시 같은 트랜잭션에서 search_outbox에 기록하고, 워커가 디바운스 후
+/// SQLite의 현재 상태로 `bulk_index_posts` 재인덱싱 (실패 시 지수 백오프 후 재시도)
+/// 사용자 변경(가입, 프로필 수정)도 같은 방식으로 사용자 검색 인덱스에 반영
+///
+/// 재인덱싱 중에는 아웃박스 처리를 멈추고, alias가 새 인덱스로 옮겨진 뒤 밀린 변경을 반영
+#[derive(Clone)]
+pub struct SearchIndexer {
+    pool: SqlitePool,
+    search_service: SearchService,
+    user_search_service: UserSearchService,
+    wakeup: Arc<Notify>,
+    /// 아웃박스 처리와 재인덱싱의 상호 배제
+    processing: Arc<Mutex<()>>,
+    reindexing: Arc<AtomicBool>,
+}
+
+impl SearchIndexer {
+    pub fn start(pool: SqlitePool, search_service: SearchService, user_search_service: UserSearchService) -> Self {
+        let indexer = Self {
+            pool,
+            search_service,
+            user_search_service,
+            wakeup: Arc::new(Notify::new()),
+            processing: Arc::new(Mutex::new(())),
+            reindexing: Arc::new(AtomicBool::new(false)),
+        };
+        tokio::spawn(run_worker(indexer.clone()));
+        indexer
+    }
+
+    /// 아웃박스 기록이 커밋된 후 워커 깨우기
+    pub fn wake(&self) {
+        self.wakeup.notify_one();
+    }
+
+    /// 새 버전 인덱스로 전체 재인덱싱 후 alias 이동 (동시에 하나만 실행)
+    pub async fn reindex(&self) -> Result<ReindexReport, String> {
+        if self.reindexing.swap(true, Ordering::SeqCst) {
+            return Err("Reindex is already in progress".to_string());
+        }
+
+        let result = {
+            let _processing = self.processing.lock().await;
+            reindex_posts(&self.pool, &self.search_service).await
+        };
+
+        self.reindexing.store(false, Ordering::SeqCst);
+        self.wake();
+        result
+    }
+
+    /// alias가 없으면(최초 실행, alias 도입 전 인덱스) 재인덱싱으로 생성
+    ///
+    /// alias 없이 쓰면 Elasticsearch가 같은 이름의 인덱스를 동적 매핑으로 만들어버리므로
+    /// 준비되기 전에는 아웃박스를 처리하지 않음
+    async fn ensure_alias(&self) -> bool {
+        let indices = self
+            .search_service
+            .es_client()
+            .alias_indices()
+            .await
+            .map_err(|e| e.to_string());
+
+        match indices {
+            Ok(indices) if !indices.is_empty() => true,
+            Ok(_) => {
+                log::info!("Search alias not found, building initial index");
+                match self.reindex().await {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::warn!("Initial search index build failed: {}", e);
+                        false
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to check search alias: {}", e);
+                false
+            }
+        }
+    }
+}
+
+async fn run_worker(indexer: SearchIndexer) {
+    let mut alias_ready = false;
+
+    loop {
+        if !alias_ready {
+            alias_ready = indexer.ensure_alias().await;
+        }
+        // 사용자 인덱스는 관리자 재인덱싱으로도 준비될 수 있으므로 서비스 상태로 확인
+        if !indexer.user_search_service.is_ready() {
+            indexer.user_search_service.ensure_index(&indexer.pool).await;
+        }
+
+        // 신호가 오면 디바운스 후 처리, 없으면 주기적으로 확인
+        if tokio::time::timeout(POLL_INTERVAL, indexer.wakeup.notified()).await.is_ok() {
+            tokio::time::sleep(DEBOUNCE).await;
+        }
+
+        // alias가 준비된 인덱스의 항목만 처리
+        let users_ready = indexer.user_search_service.is_ready();
+        let kinds: Vec<OutboxKind> = [(OutboxKind::Post, alias_ready), (OutboxKind::User, users_ready)]
+            .into_iter()
+            .filter_map(|(kind, ready)| ready.then_some(kind))
+            .collect();
+        if kinds.is_empty() {
+            continue;
+        }
+
+        let _processing = indexer.processing.lock().await;
+        let pool = &indexer.pool;
+
+        loop {
+            let entries = match OutboxEntry::claim_due(pool, &kinds, MAX_BATCH).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    log::warn!("Failed to read search outbox: {}", e);
+                    break;
+                }
+            };
+
+            if entries.is_empty() {
+                break;
+            }
+
+            let batch_full = entries.len() as i64 >= MAX_BATCH;
+            let (posts, users): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| entry.kind == OutboxKind::Post);
+            if !posts.is_empty() {
+                process_entries(pool, &indexer.search_service, posts).await;
+            }
+            if !users.is_empty() {
+                process_user_entries(pool, &indexer.user_search_service, users).await;
+            }
+
+            if !batch_full {
+                break;
+            }
+        }
+    }
+}
+
+/// 포스트 아웃박스 항목을 현재 DB 상태로 인덱스에 반영하고 결과 기록
+async fn process_entries(pool: &SqlitePool, search_service: &SearchService, entries: Vec<OutboxEntry>) {
+    let post_ids: Vec<String> = entries.iter().map(|entry| entry.target_id.clone()).collect();
+
+    let posts = match load_posts(pool, &post_ids).await {
+        Ok(posts) => posts,
+        Err(e) => {
+            log::warn!("Failed to load posts for search outbox: {}", e);
+            return;
+        }
+    };
+
+    let mut failed: HashMap<String, String> = HashMap::new();
+
+    // Box<dyn Error>는 Send가 아니므로 await 전에 문자열로 변환
+    let index_result = search_service
+        .bulk_index_posts(&posts)
+        .await
+        .map_err(|e| e.to_string());
+
+    match index_result {
+        Ok(report) => failed.extend(report.failed),
+        Err(e) => failed.extend(posts.iter().map(|post| (post.id.clone(), e.clone()))),
+    }
+
+    // DB에 없는 포스트는 삭제된 것이므로 인덱스에서도 삭제
+    let existing: HashSet<&str> = posts.iter().map(|post| post.id.as_str()).collect();
+    for post_id in post_ids.iter().filter(|id| !existing.contains(id.as_str())) {
+        let delete_result = search_service
+            .delete_post(post_id)
+            .await
+            .map_err(|e| e.to_string());
+
+        if let Err(e) = delete_result {
+            failed.insert(post_id.clone(), e);
+        }
+    }
+
+    if !failed.is_empty() {
+        log::warn!("Search indexing failed for {} of {} posts", failed.len(), entries.len());
+    }
+
+    record_results(pool, &entries, &failed).await;
+}
+
+/// 사용자 아웃박스 항목을 현재 DB 상태로 사용자 인덱스에 반영하고 결과 기록
+///
+/// 사용자는 삭제되지 않으므로 DB에 없는 항목은 그대로 완료 처리
+async fn process_user_entries(pool: &SqlitePool, user_search_service: &UserSearchService, entries: Vec<OutboxEntry>) {
+    let user_ids: Vec<String> = entries.iter().map(|entry| entry.target_id.clone()).collect();
+
+    let users = match load_users(pool, &user_ids).await {
+        Ok(users) => users,
+        Err(e) => {
+            log::warn!("Failed to load users for search outbox: {}", e);
+            return;
+        }
+    };
+
+    let mut failed: HashMap<String, String> = HashMap::new();
+
+    let index_result = user_search_service
+        .bulk_index_users(&users)
+        .await
+        .map_err(|e| e.to_string());
+
+    match index_result {
+        Ok(report) => failed.extend(report.failed),
+        Err(e) => failed.extend(users.iter().map(|user| (user.id.clone(), e.clone()))),
+    }
+
+    if !failed.is_empty() {
+        log::warn!("Search indexing failed for {} of {} users", failed.len(), entries.len());
+    }
+
+    record_results(pool, &entries, &failed).await;
+}
+
+/// 실패한 대상은 재시도 예약, 나머지는 완료 처리
+async fn record_results(pool: &SqlitePool, entries: &[OutboxEntry], failed: &HashMap<String, String>) {
+    for entry in entries {
+        let result = match failed.get(&entry.target_id) {
+            Some(error) => entry.fail(pool, error).await,
+            None => entry.complete(pool).await,
+        };
+
+        if let Err(e) = result {
+            log::warn!("Failed to update search outbox entry {}: {}", entry.id, e);
+        }
+    }
+}
+
+async fn load_posts(pool: &SqlitePool, post_ids: &[String]) -> Result<Vec<FeedPost>, sqlx::Error> {
+    if post_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let placeholders = vec!["?"; post_ids.len()].join(",");
+    let query = format!("SELECT * FROM feed_posts WHERE id IN ({})", placeholders);
+
+    let mut query_builder = sqlx::query_as::<_, FeedPost>(&query);
+    for post_id in post_ids {
+        query_builder = query_builder.bind(post_id);
+    }
+    query_builder.fetch_all(pool).await
+}
+
+async fn load_users(pool: &SqlitePool, user_ids: &[String]) -> Result<Vec<User>, sqlx::Error> {
+    if user_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let placeholders = vec!["?"; user_ids.len()].join(",");
+    let query = format!("SELECT * FROM users WHERE id IN ({})", placeholders);
+
+    let mut query_builder = sqlx::query_as::<_, User>(&query);
+    for user_id in user_ids {
+        query_builder = query_builder.bind(user_id);
+    }
+    query_builder.fetch_all(pool).await
+}
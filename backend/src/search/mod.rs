@@ -1,5 +1,17 @@
+pub mod backend;
 pub mod client;
+pub mod facets;
+pub mod indexer;
+pub mod outbox;
+pub mod personalization;
+pub mod reindex;
 pub mod service;
+pub mod sqlite;
+pub mod suggest;
+pub mod users;
 
+pub use backend::{SearchBackend, SearchBackendKind, SearchRouter};
 pub use client::ElasticsearchClient;
+pub use indexer::SearchIndexer;
 pub use service::SearchService;
+pub use users::UserSearchService;
@@ -0,0 +1,356 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::time::Duration;
+
+use crate::db::retry::RetryQueue;
+
+/// 이 횟수만큼 실패하면 DEAD 처리 (수동 재시도 필요)
+pub const MAX_ATTEMPTS: i64 = 10;
+const QUEUE: RetryQueue = RetryQueue { table: "search_outbox", max_attempts: MAX_ATTEMPTS };
+/// 워커가 항목을 가져간 뒤 다른 인스턴스가 다시 가져갈 수 있을 때까지의 시간 (처리 중 종료 대비)
+const LEASE: Duration = Duration::from_secs(2 * 60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
+#[sqlx(type_name = "TEXT")]
+pub enum OutboxStatus {
+    /// 처리 대기 (재시도 대기 포함)
+    #[sqlx(rename = "PENDING")]
+    Pending,
+    /// 재시도 한도 초과
+    #[sqlx(rename = "DEAD")]
+    Dead,
+}
+
+/// 아웃박스 항목의 인덱싱 대상
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
+#[sqlx(type_name = "TEXT")]
+pub enum OutboxKind {
+    /// 포스트 검색 인덱스
+    #[sqlx(rename = "POST")]
+    Post,
+    /// 사용자 검색 인덱스
+    #[sqlx(rename = "USER")]
+    User,
+}
+
+/// 검색 인덱싱 아웃박스 항목
+///
+/// 처리 시점의 SQLite 상태로 반영 (대상이 있으면 인덱싱, 없으면 인덱스에서 삭제)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OutboxEntry {
+    pub id: i64,
+    pub kind: OutboxKind,
+    /// 포스트 ID 또는 사용자 ID
+    pub target_id: String,
+    pub status: OutboxStatus,
+    pub version: i64,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OutboxEntry {
+    /// 포스트 변경 기록 (포스트 변경과 같은 트랜잭션에서 호출)
+    pub async fn enqueue(conn: &mut SqliteConnection, post_id: &str) -> Result<(), sqlx::Error> {
+        Self::enqueue_target(conn, OutboxKind::Post, post_id).await
+    }
+
+    /// 사용자 변경 기록 (가입, 프로필 수정과 같은 트랜잭션에서 호출)
+    pub async fn enqueue_user(conn: &mut SqliteConnection, user_id: &str) -> Result<(), sqlx::Error> {
+        Self::enqueue_target(conn, OutboxKind::User, user_id).await
+    }
+
+    /// 이미 대기 중인 항목이 있으면 버전만 올리고 즉시 처리 대상으로 변경
+    async fn enqueue_target(conn: &mut SqliteConnection, kind: OutboxKind, target_id: &str) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO search_outbox (kind, target_id, status, version, attempts, available_at, created_at, updated_at)
+             VALUES (?, ?, 'PENDING', 1, 0, ?, ?, ?)
+             ON CONFLICT(kind, target_id) WHERE status = 'PENDING' DO UPDATE SET
+                 version = version + 1,
+                 available_at = excluded.available_at,
+                 updated_at = excluded.updated_at"
+        )
+        .bind(kind)
+        .bind(target_id)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 처리할 차례가 된 항목을 가져감 (오래된 순, 처리 제한 시간 동안 다른 인스턴스에게 보이지 않음)
+    ///
+    /// 처리 도중 다시 변경되면 즉시 처리 대상이 되지만, version으로 구분하므로 이전 결과가 덮어쓰지 않음
+    /// 인덱스가 준비된 종류만 가져감 (나머지는 준비될 때까지 대기)
+    pub async fn claim_due(pool: &SqlitePool, kinds: &[OutboxKind], limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        if kinds.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let now = Utc::now();
+        let lease_until = now + chrono::Duration::from_std(LEASE).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let placeholders = vec!["?"; kinds.len()].join(",");
+        let query = format!(
+            "UPDATE search_outbox SET available_at = ?, updated_at = ?
+             WHERE id IN (
+                 SELECT id FROM search_outbox
+                 WHERE status = 'PENDING' AND available_at <= ? AND kind IN ({})
+                 ORDER BY id ASC
+                 LIMIT ?
+             )
+             RETURNING *",
+            placeholders
+        );
+
+        let mut query_builder = sqlx::query_as::<_, OutboxEntry>(&query)
+            .bind(lease_until)
+            .bind(now)
+            .bind(now);
+        for kind in kinds {
+            query_builder = query_builder.bind(*kind);
+        }
+        let mut entries = query_builder.bind(limit).fetch_all(pool).await?;
+
+        // RETURNING 순서는 보장되지 않음
+        entries.sort_by_key(|entry| entry.id);
+        Ok(entries)
+    }
+
+    /// 처리 완료 (처리 도중 다시 변경되었으면 남겨둠)
+    pub async fn complete(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM search_outbox WHERE id = ? AND version = ?")
+            .bind(self.id)
+            .bind(self.version)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 처리 실패 (백오프 후 재시도, 한도 초과 시 DEAD)
+    ///
+    /// 처리 도중 다시 변경되었으면 새 버전은 아직 시도하지 않았으므로 시도 횟수를 초기화하고 바로 처리
+    pub async fn fail(&self, pool: &SqlitePool, error: &str) -> Result<(), sqlx::Error> {
+        match QUEUE.fail(pool, self.id, Some(self.version), self.attempts, error).await? {
+            Some(true) => log::error!(
+                "Search outbox entry {} ({:?} {}) moved to dead letter after {} attempts: {}",
+                self.id,
+                self.kind,
+                self.target_id,
+                self.attempts + 1,
+                error
+            ),
+            Some(false) => {}
+            None => {
+                sqlx::query(
+                    "UPDATE search_outbox SET attempts = 0, last_error = ?, updated_at = ?
+                     WHERE id = ? AND status = 'PENDING'"
+                )
+                .bind(error)
+                .bind(Utc::now())
+                .bind(self.id)
+                .execute(pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 관리자 조회 (재시도 대기/DEAD 항목)
+    pub async fn list(
+        pool: &SqlitePool,
+        status: Option<OutboxStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, OutboxEntry>(
+            "SELECT * FROM search_outbox
+             WHERE (? IS NULL OR status = ?)
+             ORDER BY updated_at DESC, id DESC
+             LIMIT ? OFFSET ?"
+        )
+        .bind(status)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// DEAD 항목 재시도 (ids가 없으면 전체), 변경된 항목 수 반환
+    ///
+    /// 같은 대상의 대기 항목이 이미 있으면 DEAD 항목은 삭제 (대기 항목이 최신 상태를 반영)
+    pub async fn retry_dead(pool: &SqlitePool, ids: Option<&[i64]>) -> Result<u64, sqlx::Error> {
+        let dead = match ids {
+            Some([]) => return Ok(0),
+            Some(ids) => {
+                let placeholders = vec!["?"; ids.len()].join(",");
+                let query = format!(
+                    "SELECT * FROM search_outbox WHERE status = 'DEAD' AND id IN ({})",
+                    placeholders
+                );
+                let mut query_builder = sqlx::query_as::<_, OutboxEntry>(&query);
+                for id in ids {
+                    query_builder = query_builder.bind(id);
+                }
+                query_builder.fetch_all(pool).await?
+            }
+            None => {
+                sqlx::query_as::<_, OutboxEntry>("SELECT * FROM search_outbox WHERE status = 'DEAD'")
+                    .fetch_all(pool)
+                    .await?
+            }
+        };
+
+        let mut tx = pool.begin().await?;
+        for entry in &dead {
+            Self::enqueue_target(&mut tx, entry.kind, &entry.target_id).await?;
+            sqlx::query("DELETE FROM search_outbox WHERE id = ?")
+                .bind(entry.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(dead.len() as u64)
+    }
+}
+
+#[Object]
+impl OutboxEntry {
+    async fn id(&self) -> i64 {
+        self.id
+    }
+
+    async fn kind(&self) -> OutboxKind {
+        self.kind
+    }
+
+    /// 포스트 ID 또는 사용자 ID
+    async fn target_id(&self) -> &str {
+        &self.target_id
+    }
+
+    #[graphql(deprecation = "Use kind and targetId")]
+    async fn post_id(&self) -> Option<&str> {
+        (self.kind == OutboxKind::Post).then_some(self.target_id.as_str())
+    }
+
+    async fn status(&self) -> OutboxStatus {
+        self.status
+    }
+
+    async fn attempts(&self) -> i64 {
+        self.attempts
+    }
+
+    async fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// 다음 재시도 시각
+    async fn available_at(&self) -> String {
+        self.available_at.to_rfc3339()
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.to_rfc3339()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.updated_at.to_rfc3339()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_KINDS: &[OutboxKind] = &[OutboxKind::Post, OutboxKind::User];
+
+    #[tokio::test]
+    async fn test_enqueue_coalesces_and_complete_respects_version() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        OutboxEntry::enqueue(&mut conn, "post-1").await.unwrap();
+        drop(conn);
+
+        let claimed = OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        // 처리 중인 항목은 다른 인스턴스가 가져가지 않음
+        assert!(OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap().is_empty());
+
+        // 처리 중 같은 포스트가 다시 변경됨
+        let mut conn = pool.acquire().await.unwrap();
+        OutboxEntry::enqueue(&mut conn, "post-1").await.unwrap();
+        drop(conn);
+
+        claimed[0].complete(&pool).await.unwrap();
+        let remaining = OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].version, 2);
+
+        remaining[0].complete(&pool).await.unwrap();
+        assert!(OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fail_resets_attempts_when_version_moved_on() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        OutboxEntry::enqueue(&mut conn, "post-1").await.unwrap();
+        drop(conn);
+
+        let claimed = OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap();
+        claimed[0].fail(&pool, "timeout").await.unwrap();
+        let entry = &OutboxEntry::list(&pool, None, 10, 0).await.unwrap()[0];
+        assert_eq!(entry.attempts, 1);
+        assert!(entry.available_at > Utc::now());
+
+        // 이전 버전 처리 중 다시 변경되고 이전 버전은 실패
+        let mut conn = pool.acquire().await.unwrap();
+        OutboxEntry::enqueue(&mut conn, "post-1").await.unwrap();
+        drop(conn);
+
+        let mut stale = entry.clone();
+        stale.version = 1;
+        stale.fail(&pool, "timeout").await.unwrap();
+
+        let due = OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].version, due[0].attempts), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_posts_and_users_are_queued_separately() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        OutboxEntry::enqueue(&mut conn, "id-1").await.unwrap();
+        OutboxEntry::enqueue_user(&mut conn, "id-1").await.unwrap();
+        OutboxEntry::enqueue_user(&mut conn, "id-1").await.unwrap();
+        drop(conn);
+
+        // 사용자 인덱스가 준비되기 전에는 포스트만 가져감
+        let posts = OutboxEntry::claim_due(&pool, &[OutboxKind::Post], 10).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!((posts[0].kind, posts[0].target_id.as_str()), (OutboxKind::Post, "id-1"));
+
+        let due = OutboxEntry::claim_due(&pool, ALL_KINDS, 10).await.unwrap();
+        let targets: Vec<_> = due.iter().map(|entry| (entry.kind, entry.target_id.as_str(), entry.version)).collect();
+        assert_eq!(targets, vec![(OutboxKind::User, "id-1", 2)]);
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use crate::cache::FriendCache;
+use crate::models::{Category, Friendship};
+use crate::search::facets::category_keyword;
+
+/// 선호 카테고리 계산에 쓰는 최근 좋아요 수
+const RECENT_LIKES_LIMIT: i64 = 100;
+
+/// 관련도에 곱하는 배율 = 1 + 관계 + 선호 카테고리 + 최신성
+/// (작성자가 친구 / 친구의 친구이면 배율에 더하는 값)
+const FRIEND_AUTHOR_BOOST: f64 = 1.0;
+const FRIEND_OF_FRIEND_AUTHOR_BOOST: f64 = 0.3;
+/// 많이 좋아요한 순서대로 상위 카테고리에 주는 가중치
+const CATEGORY_BOOSTS: [f64; 3] = [0.5, 0.3, 0.15];
+const RECENCY_BOOST: f64 = 1.0;
+/// 최신성 감쇠 (하루까지는 최대, 이후 7일마다 절반씩 감소하는 gauss)
+const RECENCY_OFFSET_DAYS: f64 = 1.0;
+const RECENCY_SCALE_DAYS: f64 = 7.0;
+const RECENCY_DECAY: f64 = 0.5;
+
+/// 로그인한 사용자 기준 검색 순위 개인화
+#[derive(Debug, Clone, Default)]
+pub struct SearchPersonalization {
+    pub friend_ids: Vec<String>,
+    pub friend_of_friend_ids: Vec<String>,
+    /// 좋아요를 많이 한 순서
+    pub preferred_categories: Vec<Category>,
+}
+
+impl SearchPersonalization {
+    /// 친구, 친구의 친구, 최근 좋아요한 카테고리를 조회
+    ///
+    /// 친구 ID는 FriendCache 우선 (미스 시 DB 조회 후 캐시 저장)
+    pub async fn load(pool: &SqlitePool, cache: Option<&FriendCache>, user_id: &str) -> Result<Self, sqlx::Error> {
+        let friend_ids = Friendship::find_friend_ids_cached(pool, cache, user_id).await?;
+        let friend_of_friend_ids =
+            Friendship::find_friends_of_friends(pool, user_id, &friend_ids, Friendship::MAX_FRIENDS_OF_FRIENDS).await?;
+
+        let preferred_categories: Vec<Category> = sqlx::query_scalar(
+            "SELECT category FROM (
+                 SELECT p.category
+                 FROM post_likes pl
+                 INNER JOIN feed_posts p ON p.id = pl.post_id
+                 WHERE pl.user_id = ?
+                 ORDER BY pl.created_at DESC
+                 LIMIT ?
+             )
+             GROUP BY category
+             ORDER BY COUNT(*) DESC, category ASC
+             LIMIT ?"
+        )
+        .bind(user_id)
+        .bind(RECENT_LIKES_LIMIT)
+        .bind(CATEGORY_BOOSTS.len() as i64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Self {
+            friend_ids,
+            friend_of_friend_ids,
+            preferred_categories,
+        })
+    }
+
+    fn relation_boost(&self, author_id: &str) -> f64 {
+        if self.friend_ids.iter().any(|id| id == author_id) {
+            FRIEND_AUTHOR_BOOST
+        } else if self.friend_of_friend_ids.iter().any(|id| id == author_id) {
+            FRIEND_OF_FRIEND_AUTHOR_BOOST
+        } else {
+            0.0
+        }
+    }
+
+    fn category_boost(&self, category: Category) -> f64 {
+        self.preferred_categories
+            .iter()
+            .zip(CATEGORY_BOOSTS)
+            .find(|(preferred, _)| **preferred == category)
+            .map_or(0.0, |(_, boost)| boost)
+    }
+
+    /// 관련도에 곱할 배율 (Elasticsearch `score_functions`와 같은 계산, SQLite 폴백에서 사용)
+    pub fn boost(&self, author_id: &str, category: Category, created_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let age_days = (now - created_at).num_seconds() as f64 / 86_400.0;
+        let distance = (age_days.abs() - RECENCY_OFFSET_DAYS).max(0.0) / RECENCY_SCALE_DAYS;
+        let recency = RECENCY_DECAY.powf(distance * distance);
+
+        1.0 + self.relation_boost(author_id) + self.category_boost(category) + RECENCY_BOOST * recency
+    }
+
+    /// function_score 함수 목록 (score_mode: sum, boost_mode: multiply로 사용)
+    pub fn score_functions(&self) -> Vec<Value> {
+        let mut functions = vec![json!({ "weight": 1.0 })];
+
+        if !self.friend_ids.is_empty() {
+            functions.push(json!({
+                "filter": { "terms": { "author_id": self.friend_ids } },
+                "weight": FRIEND_AUTHOR_BOOST
+            }));
+        }
+        if !self.friend_of_friend_ids.is_empty() {
+            functions.push(json!({
+                "filter": { "terms": { "author_id": self.friend_of_friend_ids } },
+                "weight": FRIEND_OF_FRIEND_AUTHOR_BOOST
+            }));
+        }
+        for (category, boost) in self.preferred_categories.iter().zip(CATEGORY_BOOSTS) {
+            functions.push(json!({
+                "filter": { "term": { "category": category_keyword(*category) } },
+                "weight": boost
+            }));
+        }
+        functions.push(json!({
+            "gauss": {
+                "created_at": {
+                    "origin": "now",
+                    "offset": format!("{}d", RECENCY_OFFSET_DAYS),
+                    "scale": format!("{}d", RECENCY_SCALE_DAYS),
+                    "decay": RECENCY_DECAY
+                }
+            },
+            "weight": RECENCY_BOOST
+        }));
+
+        functions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn personalization() -> SearchPersonalization {
+        SearchPersonalization {
+            friend_ids: vec!["friend".to_string()],
+            friend_of_friend_ids: vec!["fof".to_string()],
+            preferred_categories: vec![Category::Japanese, Category::Korean],
+        }
+    }
+
+    #[test]
+    fn test_boost() {
+        let p = personalization();
+        let now = Utc::now();
+
+        // 오늘 작성된 친구의 선호 1순위 카테고리 포스트
+        assert_eq!(p.boost("friend", Category::Japanese, now, now), 1.0 + 1.0 + 0.5 + 1.0);
+        assert_eq!(p.boost("fof", Category::Korean, now, now), 1.0 + 0.3 + 0.3 + 1.0);
+        assert_eq!(p.boost("stranger", Category::Cafe, now, now), 2.0);
+
+        // 최신성은 offset(1일) 이후 scale(7일)만큼 지나면 절반
+        let week_old = p.boost("stranger", Category::Cafe, now - Duration::days(8), now);
+        assert!((week_old - 1.5).abs() < 1e-9);
+        assert!(p.boost("stranger", Category::Cafe, now - Duration::days(365), now) < 1.0 + 1e-9);
+    }
+
+    #[test]
+    fn test_score_functions() {
+        let functions = personalization().score_functions();
+        // 기본 1 + 친구 + 친구의 친구 + 카테고리 2개 + 최신성
+        assert_eq!(functions.len(), 6);
+        assert_eq!(functions[3]["filter"]["term"]["category"], "JAPANESE");
+
+        let anonymous_like = SearchPersonalization::default().score_functions();
+        assert_eq!(anonymous_like.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_falls_back_to_db_when_cache_unavailable() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        for id in ["a", "b", "c"] {
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, name, initial)
+                 VALUES (?, ? || '@example.com', 'x', ?, 'U')"
+            )
+            .bind(id)
+            .bind(id)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (user_id, friend_id) in [("a", "b"), ("b", "c")] {
+            sqlx::query("INSERT INTO friendships (user_id, friend_id) VALUES (?, ?)")
+                .bind(user_id)
+                .bind(friend_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO feed_posts (id, author_id, title, content, location, rating, category, tags)
+             VALUES ('p1', 'c', '라멘', '본문', '마포구', 4.0, 'JAPANESE', '[]')"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO post_likes (post_id, user_id) VALUES ('p1', 'a')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // 캐시에 연결할 수 없으면 DB에서 친구 ID를 조회
+        let cache = FriendCache::new("redis://127.0.0.1:1").unwrap();
+        let p = SearchPersonalization::load(&pool, Some(&cache), "a").await.unwrap();
+
+        assert_eq!(p.friend_ids, vec!["b"]);
+        assert_eq!(p.friend_of_friend_ids, vec!["c"]);
+        assert_eq!(p.preferred_categories, vec![Category::Japanese]);
+    }
+}
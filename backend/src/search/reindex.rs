@@ -0,0 +1,176 @@
+use async_graphql::SimpleObject;
+use sqlx::SqlitePool;
+
+use crate::models::FeedPost;
+use crate::search::outbox::OutboxEntry;
+use crate::search::SearchService;
+
+/// SQLite에서 한 번에 읽어 인덱싱할 포스트 수
+const PAGE_SIZE: i64 = 500;
+
+/// 재인덱싱 결과
+#[derive(Debug, Clone, Default, SimpleObject)]
+pub struct ReindexReport {
+    /// alias가 새로 가리키는 인덱스
+    pub index: String,
+    pub indexed: i64,
+    /// 인덱싱에 실패한 포스트 수 (아웃박스로 재시도)
+    pub failed: i64,
+    /// 삭제된 이전 인덱스
+    pub removed_indices: Vec<String>,
+}
+
+pub fn versioned_index_name(base: &str, version: u32) -> String {
+    format!("{}_v{}", base, version)
+}
+
+/// 기존 인덱스 이름 중 `{base}_v{n}`의 최대 n + 1 (없으면 1)
+pub fn next_index_version(base: &str, existing: &[&str]) -> u32 {
+    let prefix = format!("{}_v", base);
+    existing
+        .iter()
+        .filter_map(|name| name.strip_prefix(prefix.as_str())?.parse::<u32>().ok())
+        .max()
+        .map_or(1, |version| version + 1)
+}
+
+/// 무중단 재인덱싱
+///
+/// 현재 매핑으로 새 버전 인덱스를 만들고 SQLite의 모든 포스트를 채운 뒤,
+/// 읽기/쓰기 alias를 원자적으로 옮기고 이전 인덱스 삭제 (실패 시 새 인덱스만 정리)
+///
+/// 진행 중 변경된 포스트는 아웃박스에 쌓여 있다가 alias 이동 후 반영되므로,
+/// 호출하는 쪽에서 인덱싱 워커를 멈춘 상태로 실행해야 함
+pub async fn reindex_posts(pool: &SqlitePool, search_service: &SearchService) -> Result<ReindexReport, String> {
+    let new_index = search_service
+        .es_client()
+        .next_index_name()
+        .await
+        .map_err(|e| e.to_string())?;
+    search_service
+        .create_index(&new_index)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    log::info!("Reindexing posts into {}", new_index);
+
+    match fill_and_swap(pool, search_service, &new_index).await {
+        Ok(report) => {
+            log::info!(
+                "Reindex into {} completed: {} indexed, {} failed",
+                report.index,
+                report.indexed,
+                report.failed
+            );
+            Ok(report)
+        }
+        Err(e) => {
+            // alias는 이전 인덱스를 그대로 가리키므로 새 인덱스만 정리
+            let cleanup = search_service
+                .es_client()
+                .delete_index(&new_index)
+                .await
+                .map_err(|e| e.to_string());
+            if let Err(cleanup_error) = cleanup {
+                log::warn!("Failed to remove incomplete index {}: {}", new_index, cleanup_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+async fn fill_and_swap(
+    pool: &SqlitePool,
+    search_service: &SearchService,
+    new_index: &str,
+) -> Result<ReindexReport, String> {
+    let mut report = ReindexReport {
+        index: new_index.to_string(),
+        ..Default::default()
+    };
+    let mut failed_ids: Vec<String> = Vec::new();
+    let mut last_id = String::new();
+
+    // ID 순 키셋 페이지네이션으로 나누어 인덱싱
+    loop {
+        let posts: Vec<FeedPost> = sqlx::query_as(
+            "SELECT * FROM feed_posts WHERE id > ? ORDER BY id ASC LIMIT ?"
+        )
+        .bind(&last_id)
+        .bind(PAGE_SIZE)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let Some(last) = posts.last() else {
+            break;
+        };
+        last_id = last.id.clone();
+
+        let page = search_service
+            .bulk_index_posts_into(new_index, &posts)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        report.indexed += page.indexed as i64;
+        failed_ids.extend(page.failed.into_iter().map(|(post_id, _)| post_id));
+    }
+
+    search_service
+        .es_client()
+        .refresh_index(new_index)
+        .await
+        .map_err(|e| e.to_string())?;
+    let previous = search_service
+        .es_client()
+        .swap_alias(new_index)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // 개별 실패는 아웃박스로 넘겨 새 인덱스에 재시도
+    report.failed = failed_ids.len() as i64;
+    if !failed_ids.is_empty() {
+        if let Err(e) = enqueue_all(pool, &failed_ids).await {
+            log::warn!("Failed to enqueue {} posts after reindex: {}", failed_ids.len(), e);
+        }
+    }
+
+    for index in previous {
+        let deleted = search_service
+            .es_client()
+            .delete_index(&index)
+            .await
+            .map_err(|e| e.to_string());
+
+        match deleted {
+            Ok(()) => report.removed_indices.push(index),
+            Err(e) => log::warn!("Failed to delete previous index {}: {}", index, e),
+        }
+    }
+
+    Ok(report)
+}
+
+async fn enqueue_all(pool: &SqlitePool, post_ids: &[String]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for post_id in post_ids {
+        OutboxEntry::enqueue(&mut tx, post_id).await?;
+    }
+    tx.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_index_version() {
+        assert_eq!(next_index_version("foodie_posts", &[]), 1);
+        // alias 도입 전의 인덱스와 다른 이름의 인덱스는 무시
+        assert_eq!(
+            next_index_version("foodie_posts", &["foodie_posts", "foodie_posts_v2", "foodie_posts_v10", "foodie_posts_vx"]),
+            11
+        );
+        assert_eq!(versioned_index_name("foodie_posts", 3), "foodie_posts_v3");
+    }
+}
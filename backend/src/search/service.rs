@@ -1,12 +1,24 @@
 use crate::models::post::{Category, FeedPost};
+use crate::search::facets::{category_keyword, SearchFacets, SearchPostsFilter};
+use crate::search::personalization::SearchPersonalization;
+use crate::search::suggest::{collect_suggestions, prefix_variants, SearchSuggestion};
 use crate::search::ElasticsearchClient;
 use elasticsearch::{
-    http::request::JsonBody, BulkParts, DeleteParts, IndexParts, SearchParts,
+    http::request::JsonBody,
+    BulkParts, DeleteParts, IndexParts, SearchParts,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::error::Error;
 
+/// Elasticsearch geo_point 값
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostDocument {
     pub id: String,
@@ -14,6 +26,9 @@ pub struct PostDocument {
     pub title: String,
     pub content: String,
     pub location: String,
+    /// 위치 좌표 (geo_point, 좌표가 없는 포스트는 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_point: Option<GeoPoint>,
     pub rating: f64,
     pub category: String,
     pub tags: Vec<String>,
@@ -25,15 +40,10 @@ pub struct PostDocument {
 impl From<&FeedPost> for PostDocument {
     fn from(post: &FeedPost) -> Self {
         let tags: Vec<String> = serde_json::from_str(&post.tags).unwrap_or_default();
-        let category = match post.category {
-            Category::Korean => "KOREAN",
-            Category::Western => "WESTERN",
-            Category::Chinese => "CHINESE",
-            Category::Japanese => "JAPANESE",
-            Category::Cafe => "CAFE",
-            Category::Dessert => "DESSERT",
-        }
-        .to_string();
+        let location_point = match (post.latitude, post.longitude) {
+            (Some(lat), Some(lon)) => Some(GeoPoint { lat, lon }),
+            _ => None,
+        };
 
         Self {
             id: post.id.clone(),
@@ -41,8 +51,9 @@ impl From<&FeedPost> for PostDocument {
             title: post.title.clone(),
             content: post.content.clone(),
             location: post.location.clone(),
+            location_point,
             rating: post.rating,
-            category,
+            category: category_keyword(post.category).to_string(),
             tags,
             likes: post.likes,
             comments_count: post.comments_count,
@@ -51,9 +62,70 @@ impl From<&FeedPost> for PostDocument {
     }
 }
 
+/// 검색 결과 항목 (관련도 점수, 하이라이트 조각 포함)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub document: PostDocument,
+    /// 관련도 점수 (최신순 정렬 등 점수를 계산하지 않은 경우 None)
+    pub score: Option<f64>,
+    /// 필드별 하이라이트 조각 (`<em>`으로 감싼 HTML, 원문은 이스케이프됨)
+    pub highlights: HashMap<String, Vec<String>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
-    pub posts: Vec<PostDocument>,
+    /// Elasticsearch 랭킹 순서
+    pub hits: Vec<SearchHit>,
+    pub total: i64,
+    /// 패싯 집계 (집계를 요청하지 않은 검색은 비어 있음)
+    pub facets: SearchFacets,
+}
+
+impl SearchResult {
+    pub fn empty() -> Self {
+        Self { hits: vec![], total: 0, facets: SearchFacets::default() }
+    }
+
+    /// 검색 응답 본문에서 문서, 점수, 하이라이트 추출
+    fn from_response(response_body: &Value) -> Self {
+        let total = response_body["hits"]["total"]["value"]
+            .as_i64()
+            .unwrap_or(0);
+
+        let hits = response_body["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|hit| {
+                let document = serde_json::from_value(hit["_source"].clone()).ok()?;
+                let highlights = serde_json::from_value(hit["highlight"].clone()).unwrap_or_default();
+                Some(SearchHit {
+                    document,
+                    score: hit["_score"].as_f64(),
+                    highlights,
+                })
+            })
+            .collect();
+
+        let facets = SearchFacets::from_aggregations(&response_body["aggregations"]);
+
+        Self { hits, total, facets }
+    }
+}
+
+/// 벌크 인덱싱 결과
+#[derive(Debug, Clone, Default)]
+pub struct BulkIndexReport {
+    pub indexed: usize,
+    /// (문서 ID, 실패 사유)
+    pub failed: Vec<(String, String)>,
+}
+
+/// 거리순 주변 검색 결과
+#[derive(Debug, Clone)]
+pub struct NearbySearchResult {
+    /// (포스트 ID, 거리 km)
+    pub hits: Vec<(String, f64)>,
     pub total: i64,
 }
 
@@ -67,10 +139,18 @@ impl SearchService {
         Self { es_client }
     }
 
+    pub fn es_client(&self) -> &ElasticsearchClient {
+        &self.es_client
+    }
+
+    pub async fn health_check(&self) -> Result<(), Box<dyn Error>> {
+        self.es_client.health_check().await
+    }
+
     /// 엔터프라이즈 수준의 인덱스 생성 (최적화된 설정)
-    pub async fn create_index(&self) -> Result<(), Box<dyn Error>> {
-        let index_name = self.es_client.index_name();
-        let client = self.es_client.client();
+    ///
+    /// 버전 인덱스(`{index}_v{n}`)를 만들 때 사용하며, alias 연결은 `swap_alias`에서 처리
+    pub async fn create_index(&self, index_name: &str) -> Result<(), Box<dyn Error>> {
 
         let body = json!({
             "settings": {
@@ -109,9 +189,10 @@ impl SearchService {
                                 "한식,한국음식,korean"
                             ]
                         },
+                        // 한 글자 입력부터 자동완성되도록 1글자부터 생성
                         "edge_ngram_filter": {
                             "type": "edge_ngram",
-                            "min_gram": 2,
+                            "min_gram": 1,
                             "max_gram": 10,
                             "token_chars": ["letter", "digit"]
                         }
@@ -133,6 +214,12 @@ impl SearchService {
                             "type": "custom",
                             "tokenizer": "nori_tokenizer",
                             "filter": ["lowercase", "edge_ngram_filter"]
+                        },
+                        // 자동완성 검색용 분석기 (입력어는 n-gram으로 쪼개지 않음)
+                        "autocomplete_search": {
+                            "type": "custom",
+                            "tokenizer": "nori_tokenizer",
+                            "filter": ["lowercase"]
                         }
                     }
                 }
@@ -155,7 +242,8 @@ impl SearchService {
                             "keyword": { "type": "keyword" },
                             "autocomplete": {
                                 "type": "text",
-                                "analyzer": "autocomplete"
+                                "analyzer": "autocomplete",
+                                "search_analyzer": "autocomplete_search"
                             }
                         }
                     },
@@ -171,10 +259,14 @@ impl SearchService {
                             "keyword": { "type": "keyword" },
                             "autocomplete": {
                                 "type": "text",
-                                "analyzer": "autocomplete"
+                                "analyzer": "autocomplete",
+                                "search_analyzer": "autocomplete_search"
                             }
                         }
                     },
+                    "location_point": {
+                        "type": "geo_point"
+                    },
                     "rating": {
                         "type": "float",
                         "doc_values": true
@@ -185,7 +277,14 @@ impl SearchService {
                     },
                     "tags": {
                         "type": "keyword",
-                        "doc_values": true
+                        "doc_values": true,
+                        "fields": {
+                            "autocomplete": {
+                                "type": "text",
+                                "analyzer": "autocomplete",
+                                "search_analyzer": "autocomplete_search"
+                            }
+                        }
                     },
                     "likes": {
                         "type": "long",
@@ -204,60 +303,12 @@ impl SearchService {
             }
         });
 
-        let response = client
-            .indices()
-            .create(elasticsearch::indices::IndicesCreateParts::Index(index_name))
-            .body(body)
-            .send()
-            .await?;
-
-        if response.status_code().is_success() {
-            log::info!("Index {} created successfully with enterprise settings", index_name);
-
-            // 인덱스 alias 생성
-            self.create_alias().await?;
-        } else {
-            let text = response.text().await?;
-            log::warn!("Index creation response: {}", text);
-        }
-
-        Ok(())
-    }
-
-    /// 인덱스 Alias 생성 (무중단 재인덱싱 지원)
-    pub async fn create_alias(&self) -> Result<(), Box<dyn Error>> {
-        let index_name = self.es_client.index_name();
-        let alias_name = self.es_client.index_alias();
-        let client = self.es_client.client();
-
-        let body = json!({
-            "actions": [
-                {
-                    "add": {
-                        "index": index_name,
-                        "alias": alias_name
-                    }
-                }
-            ]
-        });
-
-        let response = client
-            .indices()
-            .update_aliases()
-            .body(body)
-            .send()
-            .await?;
-
-        if response.status_code().is_success() {
-            log::info!("Alias {} created for index {}", alias_name, index_name);
-        }
-
-        Ok(())
+        self.es_client.create_index(index_name, body).await
     }
 
     /// Index a single post
     pub async fn index_post(&self, post: &FeedPost) -> Result<(), Box<dyn Error>> {
-        let index_name = self.es_client.index_name();
+        let index_name = self.es_client.index_alias();
         let client = self.es_client.client();
         let doc = PostDocument::from(post);
 
@@ -277,7 +328,7 @@ impl SearchService {
 
     /// Delete a post from index
     pub async fn delete_post(&self, post_id: &str) -> Result<(), Box<dyn Error>> {
-        let index_name = self.es_client.index_name();
+        let index_name = self.es_client.index_alias();
         let client = self.es_client.client();
 
         let response = client
@@ -294,19 +345,27 @@ impl SearchService {
     }
 
     /// 엔터프라이즈 벌크 인덱싱 (배치 처리 + 에러 핸들링)
-    pub async fn bulk_index_posts(&self, posts: &[FeedPost]) -> Result<(), Box<dyn Error>> {
+    ///
+    /// 부분 실패 시 실패한 포스트 ID와 사유를 반환 (전체 실패 시 Err)
+    pub async fn bulk_index_posts(&self, posts: &[FeedPost]) -> Result<BulkIndexReport, Box<dyn Error>> {
+        self.bulk_index_posts_into(self.es_client.index_alias(), posts).await
+    }
+
+    /// 지정한 인덱스로 벌크 인덱싱 (재인덱싱 중 alias 연결 전의 새 인덱스에 사용)
+    pub async fn bulk_index_posts_into(
+        &self,
+        index_name: &str,
+        posts: &[FeedPost],
+    ) -> Result<BulkIndexReport, Box<dyn Error>> {
+        let mut report = BulkIndexReport::default();
         if posts.is_empty() {
-            return Ok(());
+            return Ok(report);
         }
 
         const BATCH_SIZE: usize = 500; // 엔터프라이즈 표준 배치 크기
-        let index_name = self.es_client.index_name();
         let client = self.es_client.client();
 
         // 배치로 나누어 처리
-        let mut success_count = 0;
-        let mut error_count = 0;
-
         for chunk in posts.chunks(BATCH_SIZE) {
             let mut body: Vec<JsonBody<_>> = Vec::new();
             for post in chunk {
@@ -328,11 +387,12 @@ impl SearchService {
                     for item in items {
                         if let Some(index) = item["index"].as_object() {
                             if let Some(status) = index["status"].as_i64() {
-                                if status >= 200 && status < 300 {
-                                    success_count += 1;
+                                if (200..300).contains(&status) {
+                                    report.indexed += 1;
                                 } else {
-                                    error_count += 1;
                                     log::warn!("Failed to index item: {:?}", index);
+                                    let id = index["_id"].as_str().unwrap_or_default().to_string();
+                                    report.failed.push((id, index["error"].to_string()));
                                 }
                             }
                         }
@@ -341,36 +401,40 @@ impl SearchService {
             } else {
                 let text = response.text().await?;
                 log::error!("Bulk index batch failed: {}", text);
-                error_count += chunk.len();
+                report
+                    .failed
+                    .extend(chunk.iter().map(|post| (post.id.clone(), text.clone())));
             }
         }
 
         log::info!(
             "Bulk indexing completed: {} success, {} errors",
-            success_count,
-            error_count
+            report.indexed,
+            report.failed.len()
         );
 
-        if error_count > 0 && success_count == 0 {
+        if !report.failed.is_empty() && report.indexed == 0 {
             return Err("All bulk index operations failed".into());
         }
 
-        Ok(())
+        Ok(report)
     }
 
-    /// 엔터프라이즈 검색 (최적화된 쿼리 + Highlight + Scoring)
+    /// 엔터프라이즈 검색 (최적화된 쿼리 + Highlight + Scoring + 패싯 집계)
     pub async fn search_posts(
         &self,
         query: &str,
-        category: Option<String>,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
         from: i64,
         size: i64,
     ) -> Result<SearchResult, Box<dyn Error>> {
-        let index_name = self.es_client.index_name();
+        let index_name = self.es_client.index_alias();
         let client = self.es_client.client();
 
         // Multi-match 쿼리 최적화 (필드별 가중치)
-        let mut must_queries = vec![json!({
+        let must_queries = vec![json!({
             "multi_match": {
                 "query": query,
                 "fields": ["title^5", "content^3", "location^2", "tags^4"],
@@ -382,52 +446,69 @@ impl SearchService {
             }
         })];
 
-        if let Some(cat) = category {
-            must_queries.push(json!({
-                "term": {
-                    "category": {
-                        "value": cat,
-                        "boost": 1.5
+        // 제외할 작성자 (차단 관계)
+        let must_not_queries = if excluded_author_ids.is_empty() {
+            vec![]
+        } else {
+            vec![json!({
+                "terms": {
+                    "author_id": excluded_author_ids
+                }
+            })]
+        };
+
+        let mut query = json!({
+            "bool": {
+                "must": must_queries,
+                "filter": filter.base_clauses(),
+                "must_not": must_not_queries,
+                // 인기 게시물에 가중치 부여
+                "should": [
+                    {
+                        "function_score": {
+                            "functions": [
+                                {
+                                    "field_value_factor": {
+                                        "field": "likes",
+                                        "factor": 0.5,
+                                        "modifier": "log1p",
+                                        "missing": 0
+                                    }
+                                },
+                                {
+                                    "field_value_factor": {
+                                        "field": "comments_count",
+                                        "factor": 0.3,
+                                        "modifier": "log1p",
+                                        "missing": 0
+                                    }
+                                }
+                            ],
+                            "score_mode": "sum",
+                            "boost_mode": "multiply"
+                        }
                     }
+                ]
+            }
+        });
+
+        // 로그인한 사용자: 친구, 선호 카테고리, 최신 포스트에 가중치
+        if let Some(personalization) = personalization {
+            query = json!({
+                "function_score": {
+                    "query": query,
+                    "functions": personalization.score_functions(),
+                    "score_mode": "sum",
+                    "boost_mode": "multiply"
                 }
-            }));
+            });
         }
 
         let search_body = json!({
-            "query": {
-                "bool": {
-                    "must": must_queries,
-                    // 인기 게시물에 가중치 부여
-                    "should": [
-                        {
-                            "function_score": {
-                                "functions": [
-                                    {
-                                        "field_value_factor": {
-                                            "field": "likes",
-                                            "factor": 0.5,
-                                            "modifier": "log1p",
-                                            "missing": 0
-                                        }
-                                    },
-                                    {
-                                        "field_value_factor": {
-                                            "field": "comments_count",
-                                            "factor": 0.3,
-                                            "modifier": "log1p",
-                                            "missing": 0
-                                        }
-                                    }
-                                ],
-                                "score_mode": "sum",
-                                "boost_mode": "multiply"
-                            }
-                        }
-                    ]
-                }
-            },
-            // Highlight 설정
+            "query": query,
+            // Highlight 설정 (사용자 입력이므로 원문은 HTML 이스케이프)
             "highlight": {
+                "encoder": "html",
                 "fields": {
                     "title": {
                         "fragment_size": 150,
@@ -443,6 +524,9 @@ impl SearchService {
                     }
                 }
             },
+            // 패싯 필터는 post_filter로 적용하여 집계는 필터 적용 전 기준으로 계산
+            "post_filter": filter.post_filter(),
+            "aggs": filter.aggregations(),
             // 정렬: 관련성 우선, 그다음 최신순
             "sort": [
                 { "_score": { "order": "desc" } },
@@ -466,20 +550,176 @@ impl SearchService {
         }
 
         let response_body: Value = response.json().await?;
-        let hits = response_body["hits"]["hits"].as_array().unwrap_or(&vec![]);
+        Ok(SearchResult::from_response(&response_body))
+    }
+
+    /// 검색어 자동완성 (제목, 태그, 위치)
+    ///
+    /// edge n-gram 서브필드(`*.autocomplete`)로 접두사 검색 후 일치하는 값만 추출
+    pub async fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>, Box<dyn Error>> {
+        let variants = prefix_variants(prefix);
+        if variants.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let index_name = self.es_client.index_alias();
+        let client = self.es_client.client();
+
+        let should_queries: Vec<Value> = variants
+            .iter()
+            .map(|variant| {
+                json!({
+                    "multi_match": {
+                        "query": variant,
+                        "fields": ["title.autocomplete^3", "tags.autocomplete^2", "location.autocomplete"],
+                        "operator": "and"
+                    }
+                })
+            })
+            .collect();
+
+        // 문서 하나에서 여러 후보가 나오지만 중복 제거를 고려해 여유 있게 조회
+        let search_body = json!({
+            "query": {
+                "bool": {
+                    "should": should_queries,
+                    "minimum_should_match": 1,
+                    "must_not": [
+                        { "terms": { "author_id": excluded_author_ids } }
+                    ]
+                }
+            },
+            "_source": ["title", "tags", "location"],
+            "size": (limit * 3).min(50),
+            "track_total_hits": false,
+            "timeout": "300ms"
+        });
+
+        let response = client
+            .search(SearchParts::Index(&[index_name]))
+            .body(search_body)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Search suggestions failed: {}", text).into());
+        }
+
+        let response_body: Value = response.json().await?;
+        let sources: Vec<(String, Vec<String>, String)> = response_body["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|hit| {
+                let source = &hit["_source"];
+                let tags = serde_json::from_value(source["tags"].clone()).unwrap_or_default();
+                (
+                    source["title"].as_str().unwrap_or_default().to_string(),
+                    tags,
+                    source["location"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+
+        Ok(collect_suggestions(
+            sources
+                .iter()
+                .map(|(title, tags, location)| (title.as_str(), tags.as_slice(), location.as_str())),
+            &variants,
+            limit,
+        ))
+    }
+
+    /// 주변 게시물 검색 (geo_distance 필터, 거리순 정렬)
+    pub async fn search_nearby_posts(
+        &self,
+        origin: GeoPoint,
+        radius_km: f64,
+        category: Option<Category>,
+        excluded_author_ids: &[String],
+        from: i64,
+        size: i64,
+    ) -> Result<NearbySearchResult, Box<dyn Error>> {
+        let index_name = self.es_client.index_alias();
+        let client = self.es_client.client();
+
+        let mut filters = vec![json!({
+            "geo_distance": {
+                "distance": format!("{}km", radius_km),
+                "location_point": origin
+            }
+        })];
+
+        if let Some(category) = category {
+            filters.push(json!({
+                "term": {
+                    "category": category_keyword(category)
+                }
+            }));
+        }
+
+        let search_body = json!({
+            "query": {
+                "bool": {
+                    "filter": filters,
+                    "must_not": [
+                        { "terms": { "author_id": excluded_author_ids } }
+                    ]
+                }
+            },
+            // 가까운 순, 같은 거리면 최신순
+            "sort": [
+                {
+                    "_geo_distance": {
+                        "location_point": origin,
+                        "order": "asc",
+                        "unit": "km",
+                        "distance_type": "arc"
+                    }
+                },
+                { "created_at": { "order": "desc" } }
+            ],
+            "_source": false,
+            "from": from,
+            "size": size,
+            "track_total_hits": true
+        });
+
+        let response = client
+            .search(SearchParts::Index(&[index_name]))
+            .body(search_body)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Nearby search failed: {}", text).into());
+        }
+
+        let response_body: Value = response.json().await?;
         let total = response_body["hits"]["total"]["value"]
             .as_i64()
             .unwrap_or(0);
 
-        let posts: Vec<PostDocument> = hits
-            .iter()
+        // 정렬 값의 첫 번째 항목이 거리(km)
+        let hits = response_body["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
             .filter_map(|hit| {
-                let source = &hit["_source"];
-                serde_json::from_value(source.clone()).ok()
+                let id = hit["_id"].as_str()?.to_string();
+                let distance_km = hit["sort"][0].as_f64()?;
+                Some((id, distance_km))
             })
             .collect();
 
-        Ok(SearchResult { posts, total })
+        Ok(NearbySearchResult { hits, total })
     }
 
     /// 친구 게시물 검색 (엔터프라이즈 최적화)
@@ -491,13 +731,10 @@ impl SearchService {
         size: i64,
     ) -> Result<SearchResult, Box<dyn Error>> {
         if friend_ids.is_empty() {
-            return Ok(SearchResult {
-                posts: vec![],
-                total: 0,
-            });
+            return Ok(SearchResult::empty());
         }
 
-        let index_name = self.es_client.index_name();
+        let index_name = self.es_client.index_alias();
         let client = self.es_client.client();
 
         // terms 쿼리 최적화 (대용량 친구 목록 대응)
@@ -559,6 +796,7 @@ impl SearchService {
             },
             // Highlight (검색어가 있는 경우)
             "highlight": query.as_ref().map(|_| json!({
+                "encoder": "html",
                 "fields": {
                     "title": {
                         "fragment_size": 150,
@@ -596,19 +834,6 @@ impl SearchService {
         }
 
         let response_body: Value = response.json().await?;
-        let hits = response_body["hits"]["hits"].as_array().unwrap_or(&vec![]);
-        let total = response_body["hits"]["total"]["value"]
-            .as_i64()
-            .unwrap_or(0);
-
-        let posts: Vec<PostDocument> = hits
-            .iter()
-            .filter_map(|hit| {
-                let source = &hit["_source"];
-                serde_json::from_value(source.clone()).ok()
-            })
-            .collect();
-
-        Ok(SearchResult { posts, total })
+        Ok(SearchResult::from_response(&response_body))
     }
 }
@@ -0,0 +1,556 @@
+use sqlx::{FromRow, SqlitePool};
+use std::collections::HashMap;
+
+use crate::models::FeedPost;
+use crate::search::facets::{SearchFacets, SearchPostsFilter};
+use crate::search::personalization::SearchPersonalization;
+use crate::search::service::{PostDocument, SearchHit, SearchResult};
+use crate::search::suggest::{chosung, collect_suggestions, contains_chosung, prefix_variants, SearchSuggestion};
+use crate::search::users::rank_users;
+
+/// 패싯 계산과 페이지네이션을 위해 가져오는 최대 후보 수 (total도 이 값을 넘지 않음, 사용자 검색도 동일)
+const MAX_CANDIDATES: i64 = 1000;
+/// 하이라이트 구분자 (HTML 이스케이프 후 `<em>`으로 치환)
+const HIGHLIGHT_START: char = '\u{1}';
+const HIGHLIGHT_END: char = '\u{2}';
+
+/// 컬럼별 bm25 가중치 (post_id, title, content, location, tags; Elasticsearch 필드 가중치와 동일)
+const BM25_WEIGHTS: &str = "0.0, 5.0, 3.0, 2.0, 4.0";
+
+/// 검색어를 FTS5 쿼리로 변환 (단어마다 접두사 일치, 하나라도 일치하면 결과에 포함)
+///
+/// 한국어는 조사가 붙으므로("라멘을") 접두사로 검색하고, 사용자 입력의 FTS 문법은 모두 따옴표로 무력화
+pub fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" OR "))
+    }
+}
+
+/// 자동완성용 FTS5 쿼리 (제목, 태그, 위치에서 접두사 구문 일치)
+fn fts_prefix_query(variants: &[String]) -> Option<String> {
+    let phrases: Vec<String> = variants
+        .iter()
+        .map(|variant| {
+            let words: Vec<&str> = variant
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect();
+            words.join(" ")
+        })
+        .filter(|phrase| !phrase.is_empty())
+        .map(|phrase| format!("\"{}\"*", phrase))
+        .collect();
+
+    if phrases.is_empty() {
+        None
+    } else {
+        Some(format!("{{title tags location}} : ({})", phrases.join(" OR ")))
+    }
+}
+
+/// FTS5 highlight/snippet 결과를 HTML 이스케이프 후 `<em>`으로 강조 (구분자가 없으면 None)
+fn to_highlight(marked: &str) -> Option<String> {
+    if !marked.contains(HIGHLIGHT_START) {
+        return None;
+    }
+
+    let mut html = String::with_capacity(marked.len() + 16);
+    for c in marked.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<em>"),
+            HIGHLIGHT_END => html.push_str("</em>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#x27;"),
+            _ => html.push(c),
+        }
+    }
+    Some(html)
+}
+
+#[derive(Debug, FromRow)]
+struct Candidate {
+    #[sqlx(flatten)]
+    post: FeedPost,
+    score: f64,
+    title_highlight: String,
+    content_snippet: String,
+}
+
+impl Candidate {
+    fn into_hit(self) -> SearchHit {
+        let mut highlights = HashMap::new();
+        if let Some(title) = to_highlight(&self.title_highlight) {
+            highlights.insert("title".to_string(), vec![title]);
+        }
+        if let Some(content) = to_highlight(&self.content_snippet) {
+            highlights.insert("content".to_string(), vec![content]);
+        }
+
+        SearchHit {
+            document: PostDocument::from(&self.post),
+            score: Some(self.score),
+            highlights,
+        }
+    }
+}
+
+/// 작성자 조건
+enum AuthorScope<'a> {
+    Excluding(&'a [String]),
+    Only(&'a [String]),
+}
+
+/// SQLite FTS5 검색 (Elasticsearch가 없거나 장애일 때 사용)
+///
+/// `feed_posts_fts`는 트리거로 feed_posts와 동기화되므로 별도 인덱싱이 필요 없음
+#[derive(Clone)]
+pub struct SqliteSearch {
+    pool: SqlitePool,
+}
+
+impl SqliteSearch {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 관련도 순 후보 조회 (검색어, 작성자, 작성 시각/작성자 필터 적용)
+    async fn find_candidates(
+        &self,
+        match_query: &str,
+        authors: AuthorScope<'_>,
+        filter: &SearchPostsFilter,
+    ) -> Result<Vec<Candidate>, sqlx::Error> {
+        let (author_ids, author_condition) = match authors {
+            AuthorScope::Excluding(ids) => (ids, "NOT IN"),
+            AuthorScope::Only(ids) => (ids, "IN"),
+        };
+        let author_clause = if author_ids.is_empty() {
+            match authors {
+                AuthorScope::Excluding(_) => String::new(),
+                AuthorScope::Only(_) => return Ok(vec![]),
+            }
+        } else {
+            format!(
+                "AND p.author_id {} ({})",
+                author_condition,
+                vec!["?"; author_ids.len()].join(",")
+            )
+        };
+
+        let query = format!(
+            "SELECT p.*,
+                    -bm25(feed_posts_fts, {weights}) AS score,
+                    highlight(feed_posts_fts, 1, char(1), char(2)) AS title_highlight,
+                    snippet(feed_posts_fts, 2, char(1), char(2), '…', 24) AS content_snippet
+             FROM feed_posts_fts
+             JOIN feed_posts p ON p.id = feed_posts_fts.post_id
+             WHERE feed_posts_fts MATCH ?
+             {author_clause}
+             AND (? IS NULL OR datetime(p.created_at) >= datetime(?))
+             AND (? IS NULL OR datetime(p.created_at) <= datetime(?))
+             AND (? IS NULL OR p.author_id = ?)
+             ORDER BY score DESC, p.created_at DESC
+             LIMIT ?",
+            weights = BM25_WEIGHTS,
+            author_clause = author_clause,
+        );
+
+        let mut query_builder = sqlx::query_as::<_, Candidate>(&query).bind(match_query);
+        for author_id in author_ids {
+            query_builder = query_builder.bind(author_id);
+        }
+        query_builder
+            .bind(&filter.created_after)
+            .bind(&filter.created_after)
+            .bind(&filter.created_before)
+            .bind(&filter.created_before)
+            .bind(&filter.author_id)
+            .bind(&filter.author_id)
+            .bind(MAX_CANDIDATES)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// 게시물 검색 (패싯 필터는 후보에서 적용하고, 패싯 수는 필터 적용 전 후보 기준으로 계산)
+    pub async fn search_posts(
+        &self,
+        query: &str,
+        filter: &SearchPostsFilter,
+        excluded_author_ids: &[String],
+        personalization: Option<&SearchPersonalization>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, sqlx::Error> {
+        let Some(match_query) = fts_query(query) else {
+            return Ok(SearchResult::empty());
+        };
+
+        let mut candidates = self
+            .find_candidates(&match_query, AuthorScope::Excluding(excluded_author_ids), filter)
+            .await?;
+
+        // 로그인한 사용자: Elasticsearch와 같은 배율로 관련도 조정 후 재정렬
+        if let Some(personalization) = personalization {
+            let now = chrono::Utc::now();
+            for candidate in &mut candidates {
+                candidate.score *= personalization.boost(
+                    &candidate.post.author_id,
+                    candidate.post.category,
+                    candidate.post.created_at,
+                    now,
+                );
+            }
+            candidates.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| b.post.created_at.cmp(&a.post.created_at))
+            });
+        }
+
+        let tags: Vec<Vec<String>> = candidates
+            .iter()
+            .map(|c| serde_json::from_str(&c.post.tags).unwrap_or_default())
+            .collect();
+
+        let facets = SearchFacets::count(
+            candidates
+                .iter()
+                .zip(&tags)
+                .map(|(c, tags)| (c.post.category, tags.as_slice(), c.post.rating)),
+            filter,
+        );
+
+        let matched: Vec<Candidate> = candidates
+            .into_iter()
+            .zip(&tags)
+            .filter(|(c, tags)| filter.matches_facets(c.post.category, tags, c.post.rating))
+            .map(|(c, _)| c)
+            .collect();
+
+        Ok(paginate(matched, facets, from, size))
+    }
+
+    /// 친구 게시물 검색 (검색어가 없으면 최신순)
+    pub async fn search_friend_posts(
+        &self,
+        friend_ids: &[String],
+        query: Option<&str>,
+        from: i64,
+        size: i64,
+    ) -> Result<SearchResult, sqlx::Error> {
+        if friend_ids.is_empty() {
+            return Ok(SearchResult::empty());
+        }
+
+        if let Some(match_query) = query.and_then(fts_query) {
+            let candidates = self
+                .find_candidates(&match_query, AuthorScope::Only(friend_ids), &SearchPostsFilter::default())
+                .await?;
+            return Ok(paginate(candidates, SearchFacets::default(), from, size));
+        }
+
+        let placeholders = vec!["?"; friend_ids.len()].join(",");
+
+        let count_query = format!("SELECT COUNT(*) FROM feed_posts WHERE author_id IN ({})", placeholders);
+        let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query);
+        for friend_id in friend_ids {
+            count_builder = count_builder.bind(friend_id);
+        }
+        let total = count_builder.fetch_one(&self.pool).await?;
+
+        let posts_query = format!(
+            "SELECT * FROM feed_posts
+             WHERE author_id IN ({})
+             ORDER BY created_at DESC, id DESC
+             LIMIT ? OFFSET ?",
+            placeholders
+        );
+        let mut posts_builder = sqlx::query_as::<_, FeedPost>(&posts_query);
+        for friend_id in friend_ids {
+            posts_builder = posts_builder.bind(friend_id);
+        }
+        let posts = posts_builder
+            .bind(size.max(0))
+            .bind(from.max(0))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(SearchResult {
+            hits: posts
+                .iter()
+                .map(|post| SearchHit {
+                    document: PostDocument::from(post),
+                    score: None,
+                    highlights: HashMap::new(),
+                })
+                .collect(),
+            total,
+            facets: SearchFacets::default(),
+        })
+    }
+
+    /// 검색어 자동완성 (제목, 태그, 위치)
+    pub async fn search_suggestions(
+        &self,
+        prefix: &str,
+        excluded_author_ids: &[String],
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>, sqlx::Error> {
+        let variants = prefix_variants(prefix);
+        let Some(match_query) = fts_prefix_query(&variants) else {
+            return Ok(vec![]);
+        };
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let excluded_clause = if excluded_author_ids.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "AND p.author_id NOT IN ({})",
+                vec!["?"; excluded_author_ids.len()].join(",")
+            )
+        };
+        let query = format!(
+            "SELECT p.title, p.tags, p.location
+             FROM feed_posts_fts
+             JOIN feed_posts p ON p.id = feed_posts_fts.post_id
+             WHERE feed_posts_fts MATCH ?
+             {}
+             ORDER BY bm25(feed_posts_fts, {})
+             LIMIT ?",
+            excluded_clause, BM25_WEIGHTS
+        );
+
+        let mut query_builder = sqlx::query_as::<_, (String, String, String)>(&query).bind(&match_query);
+        for author_id in excluded_author_ids {
+            query_builder = query_builder.bind(author_id);
+        }
+        let rows = query_builder
+            .bind((limit * 3).min(50) as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let sources: Vec<(String, Vec<String>, String)> = rows
+            .into_iter()
+            .map(|(title, tags, location)| (title, serde_json::from_str(&tags).unwrap_or_default(), location))
+            .collect();
+
+        Ok(collect_suggestions(
+            sources
+                .iter()
+                .map(|(title, tags, location)| (title.as_str(), tags.as_slice(), location.as_str())),
+            &variants,
+            limit,
+        ))
+    }
+
+    /// 사용자 검색 (이름/닉네임 부분 일치, 초성 일치, 친구와 친구의 친구 우선), 순위대로 ID 반환
+    ///
+    /// 저장된 초성 컬럼으로 일치하는 사용자만 SQL에서 거른 뒤(친구, 친구의 친구, 이름 접두사 순으로
+    /// 최대 MAX_CANDIDATES명) 순위 계산
+    pub async fn search_users(
+        &self,
+        query: &str,
+        friend_ids: &[String],
+        friend_of_friend_ids: &[String],
+        excluded_ids: &[String],
+        from: i64,
+        size: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        // user_match_score와 같은 정규화 (공백 제거, 소문자)
+        let normalized: String = query.split_whitespace().collect::<String>().to_lowercase();
+        if normalized.is_empty() {
+            return Ok(vec![]);
+        }
+        let query_chosung = contains_chosung(&normalized).then(|| chosung(&normalized));
+
+        let placeholders = |count: usize| vec!["?"; count].join(",");
+        let sql = format!(
+            "SELECT id, name, nickname FROM users
+             WHERE (instr(lower(replace(name, ' ', '')), ?) > 0
+                 OR instr(lower(replace(coalesce(nickname, ''), ' ', '')), ?) > 0
+                 OR instr(name_chosung, ?) > 0
+                 OR instr(nickname_chosung, ?) > 0)
+             AND id NOT IN ({})
+             ORDER BY id IN ({}) DESC, id IN ({}) DESC, instr(lower(replace(name, ' ', '')), ?) = 1 DESC, name ASC
+             LIMIT ?",
+            placeholders(excluded_ids.len()),
+            placeholders(friend_ids.len()),
+            placeholders(friend_of_friend_ids.len())
+        );
+
+        let mut query_builder = sqlx::query_as::<_, (String, String, Option<String>)>(&sql)
+            .bind(&normalized)
+            .bind(&normalized)
+            .bind(&query_chosung)
+            .bind(&query_chosung);
+        for user_id in excluded_ids.iter().chain(friend_ids).chain(friend_of_friend_ids) {
+            query_builder = query_builder.bind(user_id);
+        }
+        let candidates = query_builder
+            .bind(&normalized)
+            .bind(MAX_CANDIDATES)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rank_users(query, candidates, friend_ids, friend_of_friend_ids)
+            .into_iter()
+            .skip(from.max(0) as usize)
+            .take(size.max(0) as usize)
+            .collect())
+    }
+}
+
+fn paginate(candidates: Vec<Candidate>, facets: SearchFacets, from: i64, size: i64) -> SearchResult {
+    let total = candidates.len() as i64;
+    let hits = candidates
+        .into_iter()
+        .skip(from.max(0) as usize)
+        .take(size.max(0) as usize)
+        .map(Candidate::into_hit)
+        .collect();
+
+    SearchResult { hits, total, facets }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("강남 라멘").as_deref(), Some("\"강남\"* OR \"라멘\"*"));
+        // FTS 문법 문자는 구분자로 처리
+        assert_eq!(fts_query("\"ramen\" OR NEAR(").as_deref(), Some("\"ramen\"* OR \"OR\"* OR \"NEAR\"*"));
+        assert_eq!(fts_query("  ** "), None);
+        assert_eq!(
+            fts_prefix_query(&prefix_variants("돈코츠 람")).as_deref(),
+            Some("{title tags location} : (\"돈코츠 람\"* OR \"돈코츠 라\"*)")
+        );
+    }
+
+    #[test]
+    fn test_to_highlight() {
+        assert_eq!(
+            to_highlight("<b>\u{1}라멘\u{2}</b> & 맛집").as_deref(),
+            Some("&lt;b&gt;<em>라멘</em>&lt;/b&gt; &amp; 맛집")
+        );
+        assert_eq!(to_highlight("라멘"), None);
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_with_triggers() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        sqlx::query(
+            "INSERT INTO users (id, email, password_hash, name, initial)
+             VALUES ('u1', 'u1@example.com', 'x', 'u1', 'U')"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, title, category, tags) in [
+            ("p1", "돈코츠 라멘 맛집", "JAPANESE", r#"["라멘"]"#),
+            ("p2", "국밥 한 그릇", "KOREAN", r#"["국밥"]"#),
+            ("p3", "라멘을 먹었다", "JAPANESE", r#"[]"#),
+        ] {
+            sqlx::query(
+                "INSERT INTO feed_posts (id, author_id, title, content, location, rating, category, tags)
+                 VALUES (?, 'u1', ?, '본문', '마포구', 4.0, ?, ?)"
+            )
+            .bind(id)
+            .bind(title)
+            .bind(category)
+            .bind(tags)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let search = SqliteSearch::new(pool.clone());
+        let result = search
+            .search_posts("라멘", &SearchPostsFilter::default(), &[], None, 0, 10)
+            .await
+            .unwrap();
+        let mut ids: Vec<&str> = result.hits.iter().map(|hit| hit.document.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["p1", "p3"]);
+        assert_eq!(result.hits[0].highlights["title"][0].matches("<em>").count(), 1);
+
+        // 수정/삭제가 트리거로 반영됨
+        sqlx::query("UPDATE feed_posts SET title = '우동' WHERE id = 'p3'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM feed_posts WHERE id = 'p1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let result = search
+            .search_posts("라멘", &SearchPostsFilter::default(), &[], None, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(result.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_users_prefilter() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        for (id, name, nickname) in [
+            ("u1", "김민수", None),
+            ("u2", "김 민수", Some("Minsu")),
+            ("u3", "이영희", Some("김민수팬")),
+            ("u4", "박철수", None),
+            ("u5", "김민수", None),
+        ] {
+            sqlx::query(
+                "INSERT INTO users (id, email, password_hash, name, nickname, name_chosung, nickname_chosung, initial)
+                 VALUES (?, ?, 'x', ?, ?, ?, ?, 'U')"
+            )
+            .bind(id)
+            .bind(format!("{id}@example.com"))
+            .bind(name)
+            .bind(nickname)
+            .bind(chosung(name))
+            .bind(nickname.map(chosung))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let search = SqliteSearch::new(pool.clone());
+        let friends = vec!["u2".to_string()];
+        let excluded = vec!["u5".to_string()];
+
+        // 공백 무시 부분 일치, 친구 우선, 제외 대상은 빠짐
+        let ids = search.search_users("민수", &friends, &[], &excluded, 0, 10).await.unwrap();
+        assert_eq!(ids[0], "u2");
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec!["u1", "u2", "u3"]);
+
+        // 초성 검색은 저장된 초성 컬럼으로 걸러짐
+        let mut ids = search.search_users("ㄱㅁㅅ", &[], &[], &[], 0, 10).await.unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["u1", "u2", "u3", "u5"]);
+
+        // 닉네임 대소문자 무시
+        let ids = search.search_users("minsu", &[], &[], &[], 0, 10).await.unwrap();
+        assert_eq!(ids, vec!["u2"]);
+        assert!(search.search_users("  ", &[], &[], &[], 0, 10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+/// 한글 음절 범위 (가~힣)
+const HANGUL_SYLLABLE_START: u32 = 0xAC00;
+const HANGUL_SYLLABLE_END: u32 = 0xD7A3;
+/// 종성 개수 (없음 포함)
+const JONGSEONG_COUNT: u32 = 28;
+/// 초성 하나당 음절 수 (중성 21 × 종성 28)
+const SYLLABLES_PER_CHOSEONG: u32 = 588;
+/// 초성 순서의 호환 자모
+const CHOSEONG: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash, Enum)]
+pub enum SuggestionKind {
+    Title,
+    Tag,
+    Location,
+}
+
+/// 검색어 자동완성 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SimpleObject)]
+pub struct SearchSuggestion {
+    pub text: String,
+    pub kind: SuggestionKind,
+}
+
+/// 입력 중인 검색어의 접두사 후보
+///
+/// 한글은 다음 글자의 초성이 앞 글자 받침으로 먼저 입력되므로("라멘" 입력 중 "람")
+/// 끝 글자의 받침을 뗀 형태와, 끝의 낱자(ㄹ)를 지운 형태도 함께 사용
+pub fn prefix_variants(prefix: &str) -> Vec<String> {
+    let trimmed = prefix.trim().to_lowercase();
+    let mut variants = Vec::new();
+
+    let Some(last) = trimmed.chars().last() else {
+        return variants;
+    };
+    let head = &trimmed[..trimmed.len() - last.len_utf8()];
+    let code = last as u32;
+
+    if ('\u{3131}'..='\u{318E}').contains(&last) {
+        // 호환 자모(ㄱ~ㅣ)는 아직 조합 중인 글자
+        if !head.trim().is_empty() {
+            variants.push(head.trim_end().to_string());
+        }
+        return variants;
+    }
+
+    variants.push(trimmed.clone());
+
+    if (HANGUL_SYLLABLE_START..=HANGUL_SYLLABLE_END).contains(&code) {
+        let jongseong = (code - HANGUL_SYLLABLE_START) % JONGSEONG_COUNT;
+        if jongseong != 0 {
+            if let Some(open) = char::from_u32(code - jongseong) {
+                variants.push(format!("{}{}", head, open));
+            }
+        }
+    }
+
+    variants
+}
+
+/// 한글 음절을 초성으로 변환 ("김민수" → "ㄱㅁㅅ", 공백은 제거하고 다른 문자는 소문자로 유지)
+pub fn chosung(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            let code = c as u32;
+            if (HANGUL_SYLLABLE_START..=HANGUL_SYLLABLE_END).contains(&code) {
+                CHOSEONG[((code - HANGUL_SYLLABLE_START) / SYLLABLES_PER_CHOSEONG) as usize]
+            } else {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+        })
+        .collect()
+}
+
+/// 초성 자음이 섞인 검색어인지 ("ㄱㅁㅅ", "김ㅁ")
+pub fn contains_chosung(text: &str) -> bool {
+    text.chars().any(|c| CHOSEONG.contains(&c))
+}
+
+/// 후보 문자열이 접두사로 시작하는지 (전체 또는 단어 단위)
+fn matches_prefix(candidate: &str, variants: &[String]) -> bool {
+    let candidate = candidate.to_lowercase();
+    variants.iter().any(|variant| {
+        candidate.starts_with(variant.as_str())
+            || candidate
+                .split_whitespace()
+                .any(|word| word.starts_with(variant.as_str()))
+    })
+}
+
+/// 검색 결과 문서(관련도 순)에서 접두사와 맞는 제목/태그/위치를 중복 없이 추출
+pub fn collect_suggestions<'a, I>(sources: I, variants: &[String], limit: usize) -> Vec<SearchSuggestion>
+where
+    I: IntoIterator<Item = (&'a str, &'a [String], &'a str)>,
+{
+    let mut suggestions: Vec<SearchSuggestion> = Vec::new();
+
+    for (title, tags, location) in sources {
+        let candidates = std::iter::once((title, SuggestionKind::Title))
+            .chain(tags.iter().map(|tag| (tag.as_str(), SuggestionKind::Tag)))
+            .chain(std::iter::once((location, SuggestionKind::Location)));
+
+        for (text, kind) in candidates {
+            let text = text.trim();
+            if text.is_empty() || !matches_prefix(text, variants) {
+                continue;
+            }
+
+            let duplicate = suggestions
+                .iter()
+                .any(|s| s.kind == kind && s.text.to_lowercase() == text.to_lowercase());
+            if !duplicate {
+                suggestions.push(SearchSuggestion {
+                    text: text.to_string(),
+                    kind,
+                });
+            }
+
+            if suggestions.len() >= limit {
+                return suggestions;
+            }
+        }
+    }
+
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_variants() {
+        assert_eq!(prefix_variants("라"), vec!["라"]);
+        // "라멘" 입력 중간 상태
+        assert_eq!(prefix_variants("람"), vec!["람", "라"]);
+        assert_eq!(prefix_variants("돈코츠 라ㅁ"), vec!["돈코츠 라"]);
+        assert!(prefix_variants("ㄹ").is_empty());
+        assert_eq!(prefix_variants(" Ramen "), vec!["ramen"]);
+    }
+
+    #[test]
+    fn test_chosung() {
+        assert_eq!(chosung("김민수"), "ㄱㅁㅅ");
+        assert_eq!(chosung("김 ㅁ Soo"), "ㄱㅁsoo");
+        assert!(contains_chosung("김ㅁ"));
+        assert!(!contains_chosung("김민수"));
+        // 모음만 있는 자모는 초성이 아님
+        assert!(!contains_chosung("ㅏ"));
+    }
+
+    #[test]
+    fn test_collect_suggestions() {
+        let tags_a = vec!["라멘".to_string(), "일식".to_string()];
+        let tags_b = vec!["라멘".to_string()];
+        let sources = vec![
+            ("돈코츠 라멘 맛집", tags_a.as_slice(), "마포구 라멘야"),
+            ("라멘 두번째", tags_b.as_slice(), "강남구 역삼동"),
+        ];
+
+        let suggestions = collect_suggestions(sources.clone(), &prefix_variants("람"), 10);
+        let texts: Vec<(&str, SuggestionKind)> =
+            suggestions.iter().map(|s| (s.text.as_str(), s.kind)).collect();
+
+        assert_eq!(
+            texts,
+            vec![
+                ("돈코츠 라멘 맛집", SuggestionKind::Title),
+                ("라멘", SuggestionKind::Tag),
+                ("마포구 라멘야", SuggestionKind::Location),
+                ("라멘 두번째", SuggestionKind::Title),
+            ]
+        );
+
+        assert_eq!(collect_suggestions(sources, &prefix_variants("라"), 2).len(), 2);
+    }
+}
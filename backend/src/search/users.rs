@@ -0,0 +1,483 @@
+use elasticsearch::{http::request::JsonBody, BulkParts, SearchParts};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::models::User;
+use crate::search::reindex::ReindexReport;
+use crate::search::service::BulkIndexReport;
+use crate::search::suggest::{chosung, contains_chosung};
+use crate::search::ElasticsearchClient;
+
+/// SQLite에서 한 번에 읽어 인덱싱할 사용자 수
+const PAGE_SIZE: i64 = 500;
+/// 일치 점수에 곱하는 친구 / 친구의 친구 배율
+const FRIEND_MULTIPLIER: f64 = 3.0;
+const FRIEND_OF_FRIEND_MULTIPLIER: f64 = 1.5;
+
+/// 사용자 검색 문서 (이메일은 저장하지 않음)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDocument {
+    pub id: String,
+    pub name: String,
+    /// 이름 초성 ("김민수" → "ㄱㅁㅅ")
+    pub name_chosung: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname_chosung: Option<String>,
+}
+
+impl From<&User> for UserDocument {
+    fn from(user: &User) -> Self {
+        let nickname = user.nickname.clone().filter(|nickname| !nickname.trim().is_empty());
+        Self {
+            id: user.id.clone(),
+            name: user.name.clone(),
+            name_chosung: chosung(&user.name),
+            nickname_chosung: nickname.as_deref().map(chosung),
+            nickname,
+        }
+    }
+}
+
+/// 이름/닉네임 일치 점수 (일치하지 않으면 None)
+///
+/// Elasticsearch를 쓸 수 없을 때 사용 (완전 일치 > 접두사 > 부분 일치, 초성 검색 지원)
+pub fn user_match_score(query: &str, name: &str, nickname: Option<&str>) -> Option<f64> {
+    let query: String = query.split_whitespace().collect::<String>().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let query_chosung = contains_chosung(&query).then(|| chosung(&query));
+
+    let field_score = |value: &str| -> Option<f64> {
+        let value: String = value.split_whitespace().collect::<String>().to_lowercase();
+        if value == query {
+            Some(3.0)
+        } else if value.starts_with(&query) {
+            Some(2.0)
+        } else if value.contains(&query) {
+            Some(1.0)
+        } else {
+            let query_chosung = query_chosung.as_deref()?;
+            let value_chosung = chosung(&value);
+            if value_chosung.starts_with(query_chosung) {
+                Some(1.5)
+            } else if value_chosung.contains(query_chosung) {
+                Some(0.8)
+            } else {
+                None
+            }
+        }
+    };
+
+    let name_score = field_score(name);
+    let nickname_score = nickname.and_then(field_score).map(|score| score * 0.8);
+    match (name_score, nickname_score) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 관계 가중치 (친구 > 친구의 친구 > 그 외)
+pub fn relation_boost(user_id: &str, friend_ids: &[String], friend_of_friend_ids: &[String]) -> f64 {
+    if friend_ids.iter().any(|id| id == user_id) {
+        FRIEND_MULTIPLIER
+    } else if friend_of_friend_ids.iter().any(|id| id == user_id) {
+        FRIEND_OF_FRIEND_MULTIPLIER
+    } else {
+        1.0
+    }
+}
+
+/// 후보 (ID, 이름, 닉네임)를 일치 점수 × 관계 가중치 순으로 정렬한 ID 목록
+pub fn rank_users(
+    query: &str,
+    candidates: Vec<(String, String, Option<String>)>,
+    friend_ids: &[String],
+    friend_of_friend_ids: &[String],
+) -> Vec<String> {
+    let mut scored: Vec<(f64, String, String)> = candidates
+        .into_iter()
+        .filter_map(|(id, name, nickname)| {
+            let score = user_match_score(query, &name, nickname.as_deref())?
+                * relation_boost(&id, friend_ids, friend_of_friend_ids);
+            Some((score, name, id))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, id)| id).collect()
+}
+
+/// 사용자 검색 인덱스 (이름, 닉네임, 초성)
+#[derive(Clone)]
+pub struct UserSearchService {
+    es_client: ElasticsearchClient,
+    /// alias 존재 여부 (확인 전에는 검색과 쓰기를 하지 않음)
+    ready: Arc<AtomicBool>,
+}
+
+impl UserSearchService {
+    pub fn new(es_client: ElasticsearchClient) -> Self {
+        Self {
+            es_client,
+            ready: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// alias가 확인되어 검색과 인덱싱에 쓸 수 있는지
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// 버전 인덱스 생성 (이름은 1~2글자 n-gram으로 부분 일치)
+    pub async fn create_index(&self, index_name: &str) -> Result<(), Box<dyn Error>> {
+        let name_field = json!({
+            "type": "text",
+            "fields": {
+                "ngram": { "type": "text", "analyzer": "name_ngram" },
+                "raw": { "type": "keyword" }
+            }
+        });
+
+        let body = json!({
+            "settings": {
+                "number_of_shards": 1,
+                "number_of_replicas": 1,
+                "analysis": {
+                    "tokenizer": {
+                        "name_ngram": {
+                            "type": "ngram",
+                            "min_gram": 1,
+                            "max_gram": 2,
+                            "token_chars": ["letter", "digit"]
+                        }
+                    },
+                    "analyzer": {
+                        "name_ngram": {
+                            "type": "custom",
+                            "tokenizer": "name_ngram",
+                            "filter": ["lowercase"]
+                        }
+                    }
+                }
+            },
+            "mappings": {
+                "properties": {
+                    "id": { "type": "keyword" },
+                    "name": name_field,
+                    "name_chosung": { "type": "text", "analyzer": "name_ngram" },
+                    "nickname": name_field,
+                    "nickname_chosung": { "type": "text", "analyzer": "name_ngram" }
+                }
+            }
+        });
+
+        self.es_client.create_index(index_name, body).await
+    }
+
+    /// 사용자 문서 갱신 (검색 아웃박스 워커에서 호출)
+    ///
+    /// alias 없이 쓰면 Elasticsearch가 같은 이름의 인덱스를 동적 매핑으로 만들어버리므로
+    /// alias가 확인되기 전에는 거부
+    pub async fn bulk_index_users(&self, users: &[User]) -> Result<BulkIndexReport, Box<dyn Error>> {
+        if !self.is_ready() {
+            return Err("User search alias is not ready".into());
+        }
+        self.bulk_index_users_into(self.es_client.index_alias(), users).await
+    }
+
+    /// 지정한 인덱스로 벌크 인덱싱
+    async fn bulk_index_users_into(&self, index_name: &str, users: &[User]) -> Result<BulkIndexReport, Box<dyn Error>> {
+        let mut report = BulkIndexReport::default();
+        if users.is_empty() {
+            return Ok(report);
+        }
+
+        let mut body: Vec<JsonBody<_>> = Vec::new();
+        for user in users {
+            let doc = UserDocument::from(user);
+            body.push(json!({"index": {"_id": doc.id.clone()}}).into());
+            body.push(json!(doc).into());
+        }
+
+        let response = self
+            .es_client
+            .client()
+            .bulk(BulkParts::Index(index_name))
+            .body(body)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("Bulk user index failed: {}", text).into());
+        }
+
+        let response_body: Value = response.json().await?;
+        for item in response_body["items"].as_array().into_iter().flatten() {
+            let index = &item["index"];
+            if index["status"].as_i64().is_some_and(|status| (200..300).contains(&status)) {
+                report.indexed += 1;
+            } else {
+                let id = index["_id"].as_str().unwrap_or_default().to_string();
+                report.failed.push((id, index["error"].to_string()));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 사용자 검색 (이름/닉네임 부분 일치, 초성 일치, 친구와 친구의 친구 우선), 순위대로 ID 반환
+    pub async fn search_user_ids(
+        &self,
+        query: &str,
+        friend_ids: &[String],
+        friend_of_friend_ids: &[String],
+        excluded_ids: &[String],
+        from: i64,
+        size: i64,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut should = vec![
+            json!({ "match": { "name": { "query": query, "boost": 3 } } }),
+            json!({ "match": { "name.ngram": { "query": query, "operator": "and" } } }),
+            json!({ "match": { "nickname": { "query": query, "boost": 2 } } }),
+            json!({ "match": { "nickname.ngram": { "query": query, "operator": "and", "boost": 0.8 } } }),
+        ];
+        if contains_chosung(query) {
+            let query_chosung = chosung(query);
+            should.push(json!({ "match": { "name_chosung": { "query": query_chosung, "operator": "and" } } }));
+            should.push(json!({
+                "match": { "nickname_chosung": { "query": query_chosung, "operator": "and", "boost": 0.8 } }
+            }));
+        }
+
+        let mut functions = Vec::new();
+        if !friend_ids.is_empty() {
+            functions.push(json!({ "filter": { "ids": { "values": friend_ids } }, "weight": FRIEND_MULTIPLIER }));
+        }
+        if !friend_of_friend_ids.is_empty() {
+            functions.push(json!({
+                "filter": { "ids": { "values": friend_of_friend_ids } },
+                "weight": FRIEND_OF_FRIEND_MULTIPLIER
+            }));
+        }
+
+        let search_body = json!({
+            "query": {
+                "function_score": {
+                    "query": {
+                        "bool": {
+                            "should": should,
+                            "minimum_should_match": 1,
+                            "must_not": [{ "ids": { "values": excluded_ids } }]
+                        }
+                    },
+                    "functions": functions,
+                    "score_mode": "max",
+                    "boost_mode": "multiply"
+                }
+            },
+            "_source": false,
+            "sort": [
+                { "_score": { "order": "desc" } },
+                { "name.raw": { "order": "asc" } }
+            ],
+            "from": from,
+            "size": size
+        });
+
+        let response = self
+            .es_client
+            .client()
+            .search(SearchParts::Index(&[self.es_client.index_alias()]))
+            .body(search_body)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            let text = response.text().await?;
+            return Err(format!("User search failed: {}", text).into());
+        }
+
+        let response_body: Value = response.json().await?;
+        Ok(response_body["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|hit| hit["_id"].as_str().map(str::to_string))
+            .collect())
+    }
+
+    /// 무중단 재인덱싱 (새 버전 인덱스를 채운 뒤 alias 이동)
+    ///
+    /// 진행 중 수정된 사용자는 alias 이동 후 updated_at 기준으로 다시 반영
+    pub async fn reindex(&self, pool: &SqlitePool) -> Result<ReindexReport, String> {
+        let started_at = chrono::Utc::now();
+        let new_index = self
+            .es_client
+            .next_index_name()
+            .await
+            .map_err(|e| e.to_string())?;
+        self.create_index(&new_index).await.map_err(|e| e.to_string())?;
+
+        log::info!("Reindexing users into {}", new_index);
+
+        match self.fill_and_swap(pool, &new_index, started_at).await {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                let cleanup = self
+                    .es_client
+                    .delete_index(&new_index)
+                    .await
+                    .map_err(|e| e.to_string());
+                if let Err(cleanup_error) = cleanup {
+                    log::warn!("Failed to remove incomplete index {}: {}", new_index, cleanup_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn fill_and_swap(
+        &self,
+        pool: &SqlitePool,
+        new_index: &str,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ReindexReport, String> {
+        let mut report = ReindexReport {
+            index: new_index.to_string(),
+            ..Default::default()
+        };
+        let mut last_id = String::new();
+
+        loop {
+            let users: Vec<User> = sqlx::query_as(
+                "SELECT * FROM users WHERE id > ? ORDER BY id ASC LIMIT ?"
+            )
+            .bind(&last_id)
+            .bind(PAGE_SIZE)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            let Some(last) = users.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+
+            let bulk = self
+                .bulk_index_users_into(new_index, &users)
+                .await
+                .map_err(|e| e.to_string())?;
+            report.indexed += bulk.indexed as i64;
+            report.failed += bulk.failed.len() as i64;
+        }
+
+        self.es_client.refresh_index(new_index).await.map_err(|e| e.to_string())?;
+        let previous = self.es_client.swap_alias(new_index).await.map_err(|e| e.to_string())?;
+        self.ready.store(true, Ordering::SeqCst);
+
+        // 채우는 동안 이전 인덱스에만 반영된 변경
+        let changed: Vec<User> = sqlx::query_as("SELECT * FROM users WHERE datetime(updated_at) >= datetime(?)")
+            .bind(started_at)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Err(e) = self.bulk_index_users_into(new_index, &changed).await.map_err(|e| e.to_string()) {
+            log::warn!("Failed to re-apply {} users changed during reindex: {}", changed.len(), e);
+        }
+
+        for index in previous {
+            let deleted = self.es_client.delete_index(&index).await.map_err(|e| e.to_string());
+            match deleted {
+                Ok(()) => report.removed_indices.push(index),
+                Err(e) => log::warn!("Failed to delete previous index {}: {}", index, e),
+            }
+        }
+
+        log::info!(
+            "User reindex into {} completed: {} indexed, {} failed",
+            report.index,
+            report.indexed,
+            report.failed
+        );
+        Ok(report)
+    }
+
+    /// alias가 없으면(최초 실행) 재인덱싱으로 생성, 준비되었는지 반환 (실패 시 워커가 다시 호출)
+    pub async fn ensure_index(&self, pool: &SqlitePool) -> bool {
+        let indices = self.es_client.alias_indices().await.map_err(|e| e.to_string());
+        match indices {
+            Ok(indices) if !indices.is_empty() => {
+                self.ready.store(true, Ordering::SeqCst);
+                true
+            }
+            Ok(_) => {
+                log::info!("User search alias not found, building initial index");
+                match self.reindex(pool).await {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::warn!("Initial user index build failed: {}", e);
+                        false
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to check user search alias: {}", e);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_match_score() {
+        assert_eq!(user_match_score("김민수", "김민수", None), Some(3.0));
+        assert_eq!(user_match_score("민수", "김민수", None), Some(1.0));
+        assert_eq!(user_match_score("ㄱㅁㅅ", "김민수", None), Some(1.5));
+        assert_eq!(user_match_score("김ㅁ", "김민수", None), Some(1.5));
+        assert_eq!(user_match_score("ㅁㅅ", "김민수", None), Some(0.8));
+        assert_eq!(user_match_score("foodie", "김민수", Some("Foodie King")), Some(2.0 * 0.8));
+        assert_eq!(user_match_score("박", "김민수", None), None);
+        // 이메일 같은 다른 필드는 비교하지 않음
+        assert_eq!(user_match_score("minsu@example.com", "김민수", None), None);
+    }
+
+    #[test]
+    fn test_rank_users_boosts_friends() {
+        let candidates = vec![
+            ("u1".to_string(), "김민수".to_string(), None),
+            ("u2".to_string(), "김민수".to_string(), None),
+            ("u3".to_string(), "김민지".to_string(), None),
+            ("u4".to_string(), "박서준".to_string(), None),
+        ];
+        let friends = vec!["u3".to_string()];
+        let friends_of_friends = vec!["u2".to_string()];
+
+        // 모두 접두 일치(2.0): 친구(× 3) > 친구의 친구(× 1.5) > 그 외
+        assert_eq!(
+            rank_users("김민", candidates.clone(), &friends, &friends_of_friends),
+            vec!["u3", "u2", "u1"]
+        );
+        // 완전 일치(3.0)끼리는 친구의 친구가 먼저, 일치하지 않는 친구(u3)는 제외
+        assert_eq!(
+            rank_users("김민수", candidates, &friends, &friends_of_friends),
+            vec!["u2", "u1"]
+        );
+    }
+}
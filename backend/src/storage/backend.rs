@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::future::Future;
+
+use crate::storage::s3_client::PresignedMethod;
+use crate::storage::{LocalStorage, S3Client};
+
+pub type StorageError = Box<dyn Error + Send + Sync>;
+
+/// 이미지 저장소 (S3, 로컬 디스크)
+pub trait StorageBackend {
+    /// 업로드 후 공개 URL 반환
+    fn upload(
+        &self,
+        key: &str,
+        data: Vec<u8>,
+        content_type: &str,
+    ) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    fn delete(&self, key: &str) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    /// 객체 내용 (max_bytes보다 크면 오류)
+    fn download(&self, key: &str, max_bytes: usize) -> impl Future<Output = Result<Vec<u8>, StorageError>> + Send;
+
+    /// 객체의 공개 URL
+    fn url(&self, key: &str) -> String;
+
+    /// 만료 시간이 있는 읽기 URL
+    fn presigned_url(
+        &self,
+        key: &str,
+        expires_in_secs: u64,
+    ) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    /// 클라이언트가 직접 PUT으로 업로드할 수 있는 만료 시간이 있는 URL
+    fn presigned_upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in_secs: u64,
+    ) -> impl Future<Output = Result<String, StorageError>> + Send;
+}
+
+impl StorageBackend for S3Client {
+    async fn upload(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<String, StorageError> {
+        S3Client::upload(self, key, data, content_type)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        S3Client::delete(self, key).await.map_err(|e| e.to_string().into())
+    }
+
+    async fn download(&self, key: &str, max_bytes: usize) -> Result<Vec<u8>, StorageError> {
+        S3Client::download(self, key, max_bytes)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn url(&self, key: &str) -> String {
+        S3Client::url(self, key)
+    }
+
+    async fn presigned_url(&self, key: &str, expires_in_secs: u64) -> Result<String, StorageError> {
+        self.get_presigned_url(key, PresignedMethod::Get, expires_in_secs)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+
+    async fn presigned_upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in_secs: u64,
+    ) -> Result<String, StorageError> {
+        self.get_presigned_url(key, PresignedMethod::Put { content_type }, expires_in_secs)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+}
+
+/// 저장소 선택 (STORAGE_BACKEND 환경 변수)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackendKind {
+    S3,
+    Local,
+}
+
+impl StorageBackendKind {
+    /// 값이 비어 있으면 AWS_S3_BUCKET 설정 여부로 결정
+    pub fn parse(value: &str, s3_configured: bool) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "s3" => Some(Self::S3),
+            "local" | "disk" => Some(Self::Local),
+            "" if s3_configured => Some(Self::S3),
+            "" => Some(Self::Local),
+            _ => None,
+        }
+    }
+}
+
+/// 설정으로 선택된 저장소 (GraphQL 컨텍스트에 등록)
+#[derive(Clone)]
+pub enum Storage {
+    S3(S3Client),
+    Local(LocalStorage),
+}
+
+impl StorageBackend for Storage {
+    async fn upload(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<String, StorageError> {
+        match self {
+            Storage::S3(s3) => StorageBackend::upload(s3, key, data, content_type).await,
+            Storage::Local(local) => local.upload(key, data, content_type).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match self {
+            Storage::S3(s3) => StorageBackend::delete(s3, key).await,
+            Storage::Local(local) => local.delete(key).await,
+        }
+    }
+
+    async fn download(&self, key: &str, max_bytes: usize) -> Result<Vec<u8>, StorageError> {
+        match self {
+            Storage::S3(s3) => StorageBackend::download(s3, key, max_bytes).await,
+            Storage::Local(local) => local.download(key, max_bytes).await,
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        match self {
+            Storage::S3(s3) => StorageBackend::url(s3, key),
+            Storage::Local(local) => local.url(key),
+        }
+    }
+
+    async fn presigned_url(&self, key: &str, expires_in_secs: u64) -> Result<String, StorageError> {
+        match self {
+            Storage::S3(s3) => StorageBackend::presigned_url(s3, key, expires_in_secs).await,
+            Storage::Local(local) => local.presigned_url(key, expires_in_secs).await,
+        }
+    }
+
+    async fn presigned_upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in_secs: u64,
+    ) -> Result<String, StorageError> {
+        match self {
+            Storage::S3(s3) => StorageBackend::presigned_upload_url(s3, key, content_type, expires_in_secs).await,
+            Storage::Local(local) => local.presigned_upload_url(key, content_type, expires_in_secs).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_storage_kind() {
+        assert_eq!(StorageBackendKind::parse("S3", false), Some(StorageBackendKind::S3));
+        assert_eq!(StorageBackendKind::parse(" local ", true), Some(StorageBackendKind::Local));
+        assert_eq!(StorageBackendKind::parse("", true), Some(StorageBackendKind::S3));
+        assert_eq!(StorageBackendKind::parse("", false), Some(StorageBackendKind::Local));
+        assert_eq!(StorageBackendKind::parse("gcs", true), None);
+    }
+}
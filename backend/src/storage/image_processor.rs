@@ -1,4 +1,5 @@
-use image::{ImageFormat, DynamicImage, imageops::FilterType};
+use image::{ImageDecoder, ImageFormat, ImageReader, DynamicImage, imageops::FilterType};
+use image::metadata::Orientation;
 use std::io::Cursor;
 
 /// 이미지 해상도 변형
@@ -119,30 +120,54 @@ impl ImageProcessor {
         Ok(decoded)
     }
 
+    /// 이미지 디코딩 후 EXIF 방향 적용 (휴대폰 세로 사진이 눕지 않도록 리사이징 전에 회전)
+    ///
+    /// 픽셀만 사용해 다시 인코딩하므로 EXIF(GPS 좌표 포함), ICC 등 원본 메타데이터는 어떤 변형에도 남지 않음
+    pub fn decode(image_data: &[u8]) -> Result<DynamicImage, Box<dyn std::error::Error>> {
+        let mut decoder = ImageReader::new(Cursor::new(image_data))
+            .with_guessed_format()?
+            .into_decoder()?;
+
+        // 방향 정보가 손상된 경우 회전하지 않음
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+        let mut img = DynamicImage::from_decoder(decoder)?;
+        img.apply_orientation(orientation);
+
+        Ok(img)
+    }
+
     /// 이미지 처리 (리사이징 + 압축 + 포맷 변환)
     ///
     /// # Arguments
     /// * `image_data` - 원본 이미지 바이트
     /// * `variant` - 생성할 이미지 변형 (해상도)
     /// * `format` - 출력 포맷
+    pub fn process(
+        image_data: &[u8],
+        variant: ImageVariant,
+        format: OutputFormat,
+    ) -> Result<ProcessedImage, Box<dyn std::error::Error>> {
+        Self::process_decoded(&Self::decode(image_data)?, variant, format)
+    }
+
+    /// 디코딩된 이미지 처리
     ///
     /// # 엔터프라이즈 전략
     /// - Lanczos3 필터로 고품질 리사이징
     /// - 종횡비 유지
     /// - 최적 압축 품질
-    pub fn process(
-        image_data: &[u8],
+    fn process_decoded(
+        img: &DynamicImage,
         variant: ImageVariant,
         format: OutputFormat,
     ) -> Result<ProcessedImage, Box<dyn std::error::Error>> {
-        // 이미지 로드
-        let img = image::load_from_memory(image_data)?;
-
         // 리사이징 (필요한 경우)
         let max_width = variant.max_width();
+        let resized_image;
         let resized = if img.width() > max_width {
             // Lanczos3: 고품질 리샘플링 필터 (엔터프라이즈 권장)
-            img.resize(max_width, u32::MAX, FilterType::Lanczos3)
+            resized_image = img.resize(max_width, u32::MAX, FilterType::Lanczos3);
+            &resized_image
         } else {
             img
         };
@@ -157,7 +182,7 @@ impl ImageProcessor {
                     &mut cursor,
                     format.quality(),
                 );
-                encoder.encode_image(&resized)?;
+                encoder.encode_image(resized)?;
             }
             OutputFormat::WebP => {
                 // WebP 인코딩
@@ -197,11 +222,13 @@ impl ImageProcessor {
             OutputFormat::WebP,
         ];
 
+        // 원본은 한 번만 디코딩
+        let img = Self::decode(image_data)?;
         let mut results = Vec::new();
 
         for variant in &variants {
             for format in &formats {
-                let processed = Self::process(image_data, *variant, *format)?;
+                let processed = Self::process_decoded(&img, *variant, *format)?;
                 results.push(processed);
             }
         }
@@ -227,6 +254,27 @@ impl ImageProcessor {
             format.extension()
         )
     }
+
+    /// 이미지 하나에 대해 생성되는 모든 변형의 S3 키
+    ///
+    /// 포스트 삭제 시 업로드된 6개 변형을 모두 정리하는 데 사용
+    pub fn generate_all_s3_keys(user_id: &str, image_id: &str) -> Vec<String> {
+        let variants = [
+            ImageVariant::Thumbnail,
+            ImageVariant::Medium,
+            ImageVariant::Large,
+        ];
+        let formats = [OutputFormat::Jpeg, OutputFormat::WebP];
+
+        variants
+            .iter()
+            .flat_map(|variant| {
+                formats
+                    .iter()
+                    .map(move |format| Self::generate_s3_key(user_id, image_id, *variant, *format))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -256,4 +304,29 @@ mod tests {
         );
         assert_eq!(key, "images/user123/img456_thumb.webp");
     }
+
+    #[test]
+    fn test_applies_orientation_and_strips_metadata() {
+        use crate::storage::test_support::{sample_exif, sample_jpeg, with_exif};
+
+        // 6: 시계 방향 90도 회전 (세로로 찍은 사진)
+        let original = with_exif(&sample_jpeg(), &sample_exif(6));
+
+        for format in [OutputFormat::Jpeg, OutputFormat::WebP] {
+            let processed = ImageProcessor::process(&original, ImageVariant::Thumbnail, format).unwrap();
+            assert_eq!((processed.width, processed.height), (2, 4));
+            assert!(!processed.data.windows(4).any(|window| window == b"Exif"));
+        }
+    }
+
+    #[test]
+    fn test_all_s3_keys_generation() {
+        let keys = ImageProcessor::generate_all_s3_keys("user123", "img456");
+
+        // 3 해상도 x 2 포맷
+        assert_eq!(keys.len(), 6);
+        assert!(keys.contains(&"images/user123/img456_thumb.jpg".to_string()));
+        assert!(keys.contains(&"images/user123/img456_medium.webp".to_string()));
+        assert!(keys.contains(&"images/user123/img456_large.jpg".to_string()));
+    }
 }
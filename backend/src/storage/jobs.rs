@@ -0,0 +1,298 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::time::Duration;
+
+use crate::db::retry::RetryQueue;
+
+/// 이 횟수만큼 실패하면 DEAD 처리 (포스트 이미지 상태는 FAILED)
+pub const MAX_ATTEMPTS: i64 = 5;
+const QUEUE: RetryQueue = RetryQueue { table: "image_jobs", max_attempts: MAX_ATTEMPTS };
+/// 워커가 작업을 가져간 뒤 다른 워커가 다시 가져갈 수 있을 때까지의 시간 (처리 중 종료 대비)
+const LEASE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Eq, PartialEq, Enum)]
+#[sqlx(type_name = "TEXT")]
+pub enum ImageJobStatus {
+    /// 처리 대기 (재시도 대기 포함)
+    #[sqlx(rename = "PENDING")]
+    Pending,
+    /// 재시도 한도 초과
+    #[sqlx(rename = "DEAD")]
+    Dead,
+}
+
+/// 이미지 변형 생성 작업
+///
+/// 포스트 생성과 같은 트랜잭션에 기록하고, 워커가 저장소의 원본(source_key)으로 변형 생성
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImageJob {
+    pub id: i64,
+    pub post_id: String,
+    pub user_id: String,
+    /// 변형 키에 쓰는 이미지 ID
+    pub image_id: String,
+    /// 원본 객체 키 (처리 후 삭제)
+    pub source_key: String,
+    pub status: ImageJobStatus,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ImageJob {
+    /// 원본 객체 키: originals/{user_id}/{image_id}
+    pub fn source_key_for(user_id: &str, image_id: &str) -> String {
+        format!("originals/{}/{}", user_id, image_id)
+    }
+
+    /// 작업 기록 (포스트 생성과 같은 트랜잭션에서 호출)
+    pub async fn enqueue(
+        conn: &mut SqliteConnection,
+        post_id: &str,
+        user_id: &str,
+        image_id: &str,
+        source_key: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO image_jobs (post_id, user_id, image_id, source_key, status, attempts, available_at, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'PENDING', 0, ?, ?, ?)"
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .bind(image_id)
+        .bind(source_key)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 처리할 차례가 된 작업 하나를 가져감 (오래된 순, 처리 제한 시간 동안 다른 워커에게 보이지 않음)
+    pub async fn claim_due(pool: &SqlitePool) -> Result<Option<Self>, sqlx::Error> {
+        let now = Utc::now();
+        let lease_until = now + chrono::Duration::from_std(LEASE).unwrap_or_else(|_| chrono::Duration::zero());
+
+        sqlx::query_as::<_, ImageJob>(
+            "UPDATE image_jobs SET available_at = ?, updated_at = ?
+             WHERE id = (
+                 SELECT id FROM image_jobs
+                 WHERE status = 'PENDING' AND available_at <= ?
+                 ORDER BY id ASC
+                 LIMIT 1
+             )
+             RETURNING *"
+        )
+        .bind(lease_until)
+        .bind(now)
+        .bind(now)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// 처리 완료
+    pub async fn complete(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM image_jobs WHERE id = ?")
+            .bind(self.id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 처리 실패 (백오프 후 재시도, 한도 초과 시 DEAD) - DEAD가 되면 true
+    pub async fn fail(&self, pool: &SqlitePool, error: &str) -> Result<bool, sqlx::Error> {
+        let dead = QUEUE.fail(pool, self.id, None, self.attempts, error).await? == Some(true);
+
+        if dead {
+            log::error!(
+                "Image job {} (post {}) failed after {} attempts: {}",
+                self.id,
+                self.post_id,
+                self.attempts + 1,
+                error
+            );
+        }
+
+        Ok(dead)
+    }
+
+    /// 관리자 조회 (재시도 대기/DEAD 작업)
+    pub async fn list(
+        pool: &SqlitePool,
+        status: Option<ImageJobStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ImageJob>(
+            "SELECT * FROM image_jobs
+             WHERE (? IS NULL OR status = ?)
+             ORDER BY updated_at DESC, id DESC
+             LIMIT ? OFFSET ?"
+        )
+        .bind(status)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// DEAD 작업 재시도 (ids가 없으면 전체), 다시 대기열에 넣은 작업 반환
+    ///
+    /// 시도 횟수를 초기화하고 포스트 이미지 상태를 PROCESSING으로 되돌림
+    pub async fn retry_dead(pool: &SqlitePool, ids: Option<&[i64]>) -> Result<Vec<Self>, sqlx::Error> {
+        if ids.is_some_and(|ids| ids.is_empty()) {
+            return Ok(vec![]);
+        }
+
+        let id_filter = match ids {
+            Some(ids) => format!(" AND id IN ({})", vec!["?"; ids.len()].join(",")),
+            None => String::new(),
+        };
+        let query = format!(
+            "UPDATE image_jobs SET status = 'PENDING', attempts = 0, available_at = ?, updated_at = ?
+             WHERE status = 'DEAD'{}
+             RETURNING *",
+            id_filter
+        );
+
+        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+
+        let mut query_builder = sqlx::query_as::<_, ImageJob>(&query).bind(now).bind(now);
+        for id in ids.unwrap_or_default() {
+            query_builder = query_builder.bind(id);
+        }
+        let jobs = query_builder.fetch_all(&mut *tx).await?;
+
+        for job in &jobs {
+            sqlx::query("UPDATE feed_posts SET image_status = 'PROCESSING' WHERE id = ?")
+                .bind(&job.post_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(jobs)
+    }
+
+    /// `before` 이전에 DEAD가 된 작업 삭제 (최대 limit개), 삭제한 작업 반환 (원본은 호출한 쪽에서 삭제)
+    pub async fn delete_dead_before(
+        pool: &SqlitePool,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ImageJob>(
+            "DELETE FROM image_jobs
+             WHERE id IN (
+                 SELECT id FROM image_jobs
+                 WHERE status = 'DEAD' AND updated_at < ?
+                 LIMIT ?
+             )
+             RETURNING *"
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// 포스트 삭제 시 작업 제거 (포스트 삭제와 같은 트랜잭션에서 호출), 남은 원본 키 반환
+    pub async fn delete_for_post(conn: &mut SqliteConnection, post_id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("DELETE FROM image_jobs WHERE post_id = ? RETURNING source_key")
+            .bind(post_id)
+            .fetch_optional(conn)
+            .await
+    }
+}
+
+#[Object]
+impl ImageJob {
+    async fn id(&self) -> i64 {
+        self.id
+    }
+
+    async fn post_id(&self) -> &str {
+        &self.post_id
+    }
+
+    async fn status(&self) -> ImageJobStatus {
+        self.status
+    }
+
+    async fn attempts(&self) -> i64 {
+        self.attempts
+    }
+
+    async fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// 다음 재시도 시각
+    async fn available_at(&self) -> String {
+        self.available_at.to_rfc3339()
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.to_rfc3339()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.updated_at.to_rfc3339()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_key_for() {
+        assert_eq!(ImageJob::source_key_for("u1", "p1"), "originals/u1/p1");
+    }
+
+    #[tokio::test]
+    async fn test_fail_until_dead_and_retry() {
+        let pool = crate::db::test_support::test_pool().await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        ImageJob::enqueue(&mut conn, "p1", "u1", "p1", "originals/u1/p1").await.unwrap();
+        drop(conn);
+
+        let mut job = ImageJob::claim_due(&pool).await.unwrap().unwrap();
+        for attempt in 1..=MAX_ATTEMPTS {
+            assert_eq!(job.fail(&pool, "decode error").await.unwrap(), attempt == MAX_ATTEMPTS);
+            job.attempts = attempt;
+        }
+        assert!(ImageJob::claim_due(&pool).await.unwrap().is_none());
+
+        // 보관 기간이 지나지 않은 DEAD 작업은 남아 있음
+        let an_hour_ago = Utc::now() - chrono::Duration::hours(1);
+        assert!(ImageJob::delete_dead_before(&pool, an_hour_ago, 10).await.unwrap().is_empty());
+
+        let retried = ImageJob::retry_dead(&pool, None).await.unwrap();
+        assert_eq!(retried.len(), 1);
+        assert_eq!((retried[0].status, retried[0].attempts), (ImageJobStatus::Pending, 0));
+        let job = ImageJob::claim_due(&pool).await.unwrap().unwrap();
+
+        // 다시 DEAD가 된 뒤 보관 기간이 지나면 삭제되고 원본 키 반환
+        sqlx::query("UPDATE image_jobs SET status = 'DEAD', updated_at = ? WHERE id = ?")
+            .bind(an_hour_ago - chrono::Duration::seconds(1))
+            .bind(job.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let removed = ImageJob::delete_dead_before(&pool, an_hour_ago, 10).await.unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].source_key, "originals/u1/p1");
+    }
+}
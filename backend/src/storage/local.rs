@@ -0,0 +1,268 @@
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use crate::storage::backend::{StorageBackend, StorageError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 업로드 URL 서명 키 최소 길이 (바이트)
+pub const MIN_SIGNING_KEY_BYTES: usize = 32;
+
+/// 로컬 디스크 저장소
+///
+/// 개발/테스트용: AWS 없이 `root` 아래에 저장하고 actix 정적 라우트(`/uploads`)로 제공
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    /// 정적 라우트의 공개 URL (예: http://127.0.0.1:8080/uploads)
+    public_url: String,
+    /// 업로드 URL 서명 키 (`LOCAL_STORAGE_SIGNING_KEY`, 인스턴스끼리 같아야 함)
+    signing_key: Vec<u8>,
+}
+
+impl LocalStorage {
+    /// 정적 파일 라우트 경로
+    pub const ROUTE: &'static str = "/uploads";
+
+    pub fn new(root: impl Into<PathBuf>, public_url: &str, signing_key: &[u8]) -> Result<Self, String> {
+        if signing_key.len() < MIN_SIGNING_KEY_BYTES {
+            return Err(format!("Signing key must be at least {} bytes", MIN_SIGNING_KEY_BYTES));
+        }
+
+        Ok(Self {
+            root: root.into(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+            signing_key: signing_key.to_vec(),
+        })
+    }
+
+    /// 정적 라우트로 제공하는 최상위 디렉터리 (처리된 변형만 공개)
+    ///
+    /// 원본(`originals/`, `uploads/`)은 EXIF(GPS 좌표 포함)가 남아 있으므로 제공하지 않음
+    const PUBLIC_DIR: &'static str = "images";
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 정적 라우트로 제공해도 되는 경로인지 (root 기준 상대 경로)
+    pub fn is_public(path: &Path) -> bool {
+        matches!(path.components().next(), Some(Component::Normal(dir)) if dir == Self::PUBLIC_DIR)
+    }
+
+    /// 키를 저장 경로로 변환 (`..`, 절대 경로 등 root 밖을 가리키는 키는 거부)
+    fn resolve(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(format!("Invalid storage key: {}", key).into());
+        }
+
+        Ok(self.root.join(relative))
+    }
+
+    fn upload_mac(&self, key: &str, content_type: &str, expires_at: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length");
+        mac.update(format!("PUT\n{}\n{}\n{}", key, content_type, expires_at).as_bytes());
+        mac
+    }
+
+    fn upload_signature(&self, key: &str, content_type: &str, expires_at: i64) -> String {
+        hex::encode(self.upload_mac(key, content_type, expires_at).finalize().into_bytes())
+    }
+
+    /// 서명된 업로드 URL 검증 (키, Content-Type, 만료 시각이 발급 때와 같아야 함)
+    pub fn verify_upload(&self, key: &str, content_type: &str, expires_at: i64, signature: &str, now: i64) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        now <= expires_at
+            && self
+                .upload_mac(key, content_type, expires_at)
+                .verify_slice(&signature)
+                .is_ok()
+    }
+}
+
+/// 서명된 업로드 URL 쿼리
+#[derive(Debug, Deserialize)]
+pub struct SignedUploadQuery {
+    expires: i64,
+    signature: String,
+}
+
+/// `PUT /uploads/{key}` - presigned_upload_url로 발급한 URL로 직접 업로드
+pub async fn put_object(
+    storage: web::Data<LocalStorage>,
+    key: web::Path<String>,
+    query: web::Query<SignedUploadQuery>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> HttpResponse {
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let now = chrono::Utc::now().timestamp();
+    if !storage.verify_upload(&key, content_type, query.expires, &query.signature, now) {
+        return HttpResponse::Forbidden().body("Invalid or expired upload URL");
+    }
+
+    match storage.upload(&key, body.to_vec(), content_type).await {
+        Ok(_) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            log::warn!("Failed to store upload {}: {}", key, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+impl StorageBackend for LocalStorage {
+    async fn upload(&self, key: &str, data: Vec<u8>, _content_type: &str) -> Result<String, StorageError> {
+        let path = self.resolve(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, data).await?;
+
+        Ok(self.url(key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match tokio::fs::remove_file(self.resolve(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn download(&self, key: &str, max_bytes: usize) -> Result<Vec<u8>, StorageError> {
+        let path = self.resolve(key)?;
+        if tokio::fs::metadata(&path).await?.len() > max_bytes as u64 {
+            return Err(format!("Object must be at most {} bytes", max_bytes).into());
+        }
+        Ok(tokio::fs::read(path).await?)
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+
+    /// 변형은 공개 라우트로 제공되므로 만료 없이 공개 URL 반환 (원본은 제공하지 않으므로 URL 없음)
+    async fn presigned_url(&self, key: &str, _expires_in_secs: u64) -> Result<String, StorageError> {
+        self.resolve(key)?;
+        if !Self::is_public(Path::new(key)) {
+            return Err(format!("Object is not publicly served: {}", key).into());
+        }
+        Ok(self.url(key))
+    }
+
+    /// `PUT /uploads/{key}?expires=..&signature=..` (HMAC-SHA256 서명)
+    async fn presigned_upload_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        expires_in_secs: u64,
+    ) -> Result<String, StorageError> {
+        self.resolve(key)?;
+        let expires_at = chrono::Utc::now().timestamp() + expires_in_secs as i64;
+        let signature = self.upload_signature(key, content_type, expires_at);
+
+        Ok(format!("{}?expires={}&signature={}", self.url(key), expires_at, signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNING_KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_only_variants_are_public() {
+        assert!(LocalStorage::is_public(Path::new("images/u1/p1_thumb.jpg")));
+        assert!(!LocalStorage::is_public(Path::new("originals/u1/p1")));
+        assert!(!LocalStorage::is_public(Path::new("uploads/u1/a/original")));
+        assert!(!LocalStorage::is_public(Path::new("")));
+    }
+
+    #[test]
+    fn test_resolve_rejects_escaping_keys() {
+        let storage = LocalStorage::new("/tmp/foodie", "http://localhost:8080/uploads/", &SIGNING_KEY).unwrap();
+
+        assert_eq!(
+            storage.resolve("images/u1/p1_thumb.jpg").unwrap(),
+            PathBuf::from("/tmp/foodie/images/u1/p1_thumb.jpg")
+        );
+        assert!(storage.resolve("../etc/passwd").is_err());
+        assert!(storage.resolve("images/../../etc/passwd").is_err());
+        assert!(storage.resolve("/etc/passwd").is_err());
+        assert!(storage.resolve("").is_err());
+        assert_eq!(
+            storage.url("images/u1/p1_thumb.jpg"),
+            "http://localhost:8080/uploads/images/u1/p1_thumb.jpg"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_presigned_upload_url() {
+        let storage = LocalStorage::new("/tmp/foodie", "/uploads", &SIGNING_KEY).unwrap();
+        let url = storage
+            .presigned_upload_url("uploads/u1/a/original", "image/jpeg", 60)
+            .await
+            .unwrap();
+
+        let (path, query) = url.split_once('?').unwrap();
+        assert_eq!(path, "/uploads/uploads/u1/a/original");
+        let params: std::collections::HashMap<&str, &str> =
+            query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+        let expires_at: i64 = params["expires"].parse().unwrap();
+        let signature = params["signature"];
+        let now = chrono::Utc::now().timestamp();
+
+        assert!(storage.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at, signature, now));
+        assert!(!storage.verify_upload("uploads/u1/b/original", "image/jpeg", expires_at, signature, now));
+        assert!(!storage.verify_upload("uploads/u1/a/original", "image/png", expires_at, signature, now));
+        assert!(!storage.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at + 1, signature, now));
+        assert!(!storage.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at, signature, expires_at + 1));
+        assert!(!storage.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at, "not-hex", now));
+
+        // 같은 키를 쓰는 다른 인스턴스에서도 검증되고, 다른 키로는 실패
+        let other = LocalStorage::new("/tmp/foodie", "/uploads", &SIGNING_KEY).unwrap();
+        assert!(other.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at, signature, now));
+        let rotated = LocalStorage::new("/tmp/foodie", "/uploads", &[8; 32]).unwrap();
+        assert!(!rotated.verify_upload("uploads/u1/a/original", "image/jpeg", expires_at, signature, now));
+        assert!(LocalStorage::new("/tmp/foodie", "/uploads", b"short").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_and_delete() {
+        let root = std::env::temp_dir().join(format!("foodie-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalStorage::new(&root, "/uploads", &SIGNING_KEY).unwrap();
+
+        let url = storage.upload("images/u1/a.jpg", vec![1, 2, 3], "image/jpeg").await.unwrap();
+        assert_eq!(url, "/uploads/images/u1/a.jpg");
+        assert_eq!(std::fs::read(root.join("images/u1/a.jpg")).unwrap(), vec![1, 2, 3]);
+        assert_eq!(storage.download("images/u1/a.jpg", 3).await.unwrap(), vec![1, 2, 3]);
+        assert!(storage.download("images/u1/a.jpg", 2).await.is_err());
+
+        storage.delete("images/u1/a.jpg").await.unwrap();
+        assert!(!root.join("images/u1/a.jpg").exists());
+        // 이미 없는 파일 삭제는 성공
+        storage.delete("images/u1/a.jpg").await.unwrap();
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
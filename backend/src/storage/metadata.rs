@@ -0,0 +1,110 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use exif::{Exif, In, Reader, Tag, Value};
+use std::io::Cursor;
+
+use crate::models::geo::validate_coordinates;
+
+/// 촬영 시각 입출력 형식 (EXIF에는 시간대가 없을 수 있어 기기 현지 시각 그대로 사용)
+const TAKEN_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// 사진 EXIF에서 읽은 촬영 정보
+///
+/// 사용자가 동의한 경우에만 읽으며, 포스트 입력의 추천 값으로만 사용
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhotoMetadata {
+    pub taken_at: Option<NaiveDateTime>,
+    /// (위도, 경도)
+    pub coordinates: Option<(f64, f64)>,
+}
+
+impl PhotoMetadata {
+    /// JPEG, PNG, WebP의 EXIF 읽기 (EXIF가 없거나 손상되었으면 빈 값)
+    pub fn read(image_data: &[u8]) -> Self {
+        match Reader::new().read_from_container(&mut Cursor::new(image_data)) {
+            Ok(exif) => Self {
+                taken_at: read_taken_at(&exif),
+                coordinates: read_coordinates(&exif),
+            },
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+pub fn format_taken_at(taken_at: &NaiveDateTime) -> String {
+    taken_at.format(TAKEN_AT_FORMAT).to_string()
+}
+
+pub fn parse_taken_at(value: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(value, TAKEN_AT_FORMAT)
+        .map_err(|_| "takenAt must be in YYYY-MM-DDTHH:MM:SS format".to_string())
+}
+
+fn read_taken_at(exif: &Exif) -> Option<NaiveDateTime> {
+    [Tag::DateTimeOriginal, Tag::DateTime].iter().find_map(|tag| {
+        let Value::Ascii(ref values) = exif.get_field(*tag, In::PRIMARY)?.value else {
+            return None;
+        };
+        let taken_at = exif::DateTime::from_ascii(values.first()?).ok()?;
+
+        NaiveDate::from_ymd_opt(taken_at.year.into(), taken_at.month.into(), taken_at.day.into())?
+            .and_hms_opt(taken_at.hour.into(), taken_at.minute.into(), taken_at.second.into())
+    })
+}
+
+fn read_coordinates(exif: &Exif) -> Option<(f64, f64)> {
+    let latitude = read_gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, b'S')?;
+    let longitude = read_gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, b'W')?;
+
+    validate_coordinates(latitude, longitude).ok()?;
+    Some((latitude, longitude))
+}
+
+/// 도/분/초 값과 방향(N/S, E/W)으로 십진 좌표 계산
+fn read_gps_coordinate(exif: &Exif, value_tag: Tag, ref_tag: Tag, negative_ref: u8) -> Option<f64> {
+    let Value::Rational(ref dms) = exif.get_field(value_tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    if dms.len() < 3 || dms.iter().any(|part| part.denom == 0) {
+        return None;
+    }
+
+    let degrees = dms_to_degrees(dms[0].to_f64(), dms[1].to_f64(), dms[2].to_f64());
+    let is_negative = match exif.get_field(ref_tag, In::PRIMARY).map(|field| &field.value) {
+        Some(Value::Ascii(values)) => values.first().and_then(|value| value.first()) == Some(&negative_ref),
+        _ => false,
+    };
+
+    Some(if is_negative { -degrees } else { degrees })
+}
+
+fn dms_to_degrees(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    degrees + minutes / 60.0 + seconds / 3600.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_support::{sample_exif, sample_jpeg, with_exif};
+
+    #[test]
+    fn test_read_photo_metadata() {
+        let metadata = PhotoMetadata::read(&with_exif(&sample_jpeg(), &sample_exif(1)));
+
+        assert_eq!(
+            metadata.taken_at.map(|taken_at| format_taken_at(&taken_at)),
+            Some("2024-05-01T12:30:15".to_string())
+        );
+        let (latitude, longitude) = metadata.coordinates.unwrap();
+        assert!((latitude - 37.566333).abs() < 1e-5);
+        assert!((longitude - 126.979667).abs() < 1e-5);
+
+        assert_eq!(PhotoMetadata::read(&sample_jpeg()), PhotoMetadata::default());
+    }
+
+    #[test]
+    fn test_parse_taken_at() {
+        let taken_at = parse_taken_at("2024-05-01T12:30:15").unwrap();
+        assert_eq!(format_taken_at(&taken_at), "2024-05-01T12:30:15");
+        assert!(parse_taken_at("2024-05-01").is_err());
+    }
+}
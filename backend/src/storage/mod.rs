@@ -1,5 +1,19 @@
+pub mod backend;
 pub mod s3_client;
+pub mod local;
 pub mod image_processor;
+pub mod pipeline;
+pub mod upload;
+pub mod metadata;
+pub mod jobs;
+pub mod worker;
+pub mod sweeper;
+/// 테스트 공용 도구 (EXIF가 있는 샘플 이미지)
+#[cfg(test)]
+pub(crate) mod test_support;
 
+pub use backend::{Storage, StorageBackend, StorageBackendKind};
 pub use s3_client::S3Client;
+pub use local::LocalStorage;
+pub use worker::ImageWorker;
 pub use image_processor::{ImageProcessor, ImageVariant, OutputFormat, ProcessedImage};
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::models::post::{ImageFormatUrls, ImageUrls};
+use crate::storage::{ImageProcessor, ImageVariant, OutputFormat, StorageBackend};
+
+/// 원본 이미지를 모든 변형(3 해상도 x 2 포맷)으로 처리해 저장소에 업로드
+///
+/// 이미지 ID는 포스트 ID 또는 직접 업로드 ID (삭제 시 같은 키로 정리)
+/// 디코딩/리사이즈/인코딩은 blocking 스레드 풀에서 실행 (async 실행기를 막지 않음)
+pub async fn upload_image_variants<S: StorageBackend>(
+    storage: &S,
+    user_id: &str,
+    image_id: &str,
+    image_data: Vec<u8>,
+) -> Result<ImageUrls, String> {
+    let processed_images = tokio::task::spawn_blocking(move || {
+        ImageProcessor::process_all_variants(&image_data).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Failed to process images: {}", e))?
+    .map_err(|e| format!("Failed to process images: {}", e))?;
+
+    let mut url_map = HashMap::new();
+    for img in processed_images {
+        let key = ImageProcessor::generate_s3_key(user_id, image_id, img.variant, img.format);
+
+        let url = storage
+            .upload(&key, img.data, img.format.content_type())
+            .await
+            .map_err(|e| format!("Failed to upload image: {}", e))?;
+
+        url_map.insert((img.variant, img.format), url);
+    }
+
+    let mut format_urls = |variant: ImageVariant| -> Result<ImageFormatUrls, String> {
+        let mut url = |format: OutputFormat| {
+            url_map
+                .remove(&(variant, format))
+                .ok_or_else(|| format!("Missing {:?} {:?} image", variant, format))
+        };
+        Ok(ImageFormatUrls {
+            webp: url(OutputFormat::WebP)?,
+            jpeg: url(OutputFormat::Jpeg)?,
+        })
+    };
+
+    Ok(ImageUrls {
+        thumbnail: format_urls(ImageVariant::Thumbnail)?,
+        medium: format_urls(ImageVariant::Medium)?,
+        large: format_urls(ImageVariant::Large)?,
+    })
+}
+
+/// 이미지의 모든 변형 삭제 (실패한 키는 로그만 남김)
+pub async fn delete_image_variants<S: StorageBackend>(storage: &S, user_id: &str, image_id: &str) {
+    for key in ImageProcessor::generate_all_s3_keys(user_id, image_id) {
+        if let Err(e) = storage.delete(&key).await {
+            log::warn!("Failed to delete stored image {}: {}", key, e);
+        }
+    }
+}
@@ -2,6 +2,13 @@ use aws_sdk_s3::{Client, config::Region};
 use aws_config::BehaviorVersion;
 use std::env;
 
+/// Pre-signed URL로 허용할 요청
+#[derive(Debug, Clone, Copy)]
+pub enum PresignedMethod<'a> {
+    Get,
+    Put { content_type: &'a str },
+}
+
 /// S3 클라이언트 래퍼
 ///
 /// 엔터프라이즈급 이미지 저장을 위한 S3 클라이언트
@@ -25,7 +32,7 @@ impl S3Client {
         let region = env::var("AWS_REGION")
             .unwrap_or_else(|_| "ap-northeast-2".to_string());
         let bucket = env::var("AWS_S3_BUCKET")
-            .expect("AWS_S3_BUCKET must be set");
+            .map_err(|_| "AWS_S3_BUCKET must be set")?;
 
         let config = aws_config::defaults(BehaviorVersion::latest())
             .region(Region::new(region))
@@ -58,8 +65,12 @@ impl S3Client {
             .send()
             .await?;
 
-        // CloudFront URL 또는 S3 URL 반환
-        let url = if let Ok(cloudfront_domain) = env::var("AWS_CLOUDFRONT_DOMAIN") {
+        Ok(self.url(key))
+    }
+
+    /// 객체의 공개 URL (CloudFront 도메인이 있으면 CDN URL)
+    pub fn url(&self, key: &str) -> String {
+        if let Ok(cloudfront_domain) = env::var("AWS_CLOUDFRONT_DOMAIN") {
             // CloudFront CDN 사용 (엔터프라이즈 권장)
             format!("https://{}/{}", cloudfront_domain, key)
         } else {
@@ -70,9 +81,7 @@ impl S3Client {
                 env::var("AWS_REGION").unwrap_or_else(|_| "ap-northeast-2".to_string()),
                 key
             )
-        };
-
-        Ok(url)
+        }
     }
 
     /// S3에서 파일 삭제
@@ -90,32 +99,65 @@ impl S3Client {
         Ok(())
     }
 
+    /// S3 객체 다운로드 (max_bytes보다 크면 받지 않고 오류)
+    pub async fn download(&self, key: &str, max_bytes: usize) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let output = self.client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+
+        if output.content_length().unwrap_or(0) > max_bytes as i64 {
+            return Err(format!("Object must be at most {} bytes", max_bytes).into());
+        }
+
+        let data = output.body.collect().await?.into_bytes();
+        Ok(data.to_vec())
+    }
+
     /// Pre-signed URL 생성 (보안 강화)
     ///
     /// 엔터프라이즈 전략: 직접 S3 URL 대신 만료 시간이 있는 서명된 URL 사용
+    /// - GET: 비공개 객체 읽기
+    /// - PUT: 클라이언트가 서버를 거치지 않고 버킷에 직접 업로드 (Content-Type 헤더가 서명에 포함됨)
     ///
     /// # Arguments
     /// * `key` - S3 객체 키
+    /// * `method` - 서명할 요청 (GET 또는 PUT)
     /// * `expires_in_secs` - URL 만료 시간 (초 단위, 기본 3600초 = 1시간)
     pub async fn get_presigned_url(
         &self,
         key: &str,
+        method: PresignedMethod<'_>,
         expires_in_secs: u64,
     ) -> Result<String, Box<dyn std::error::Error>> {
         use aws_sdk_s3::presigning::PresigningConfig;
         use std::time::Duration;
 
-        let presigned_request = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .presigned(
-                PresigningConfig::builder()
-                    .expires_in(Duration::from_secs(expires_in_secs))
-                    .build()?,
-            )
-            .await?;
+        let config = PresigningConfig::builder()
+            .expires_in(Duration::from_secs(expires_in_secs))
+            .build()?;
+
+        let presigned_request = match method {
+            PresignedMethod::Get => {
+                self.client
+                    .get_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .presigned(config)
+                    .await?
+            }
+            PresignedMethod::Put { content_type } => {
+                self.client
+                    .put_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .content_type(content_type)
+                    .presigned(config)
+                    .await?
+            }
+        };
 
         Ok(presigned_request.uri().to_string())
     }
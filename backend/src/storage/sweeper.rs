@@ -0,0 +1,89 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+use crate::models::ImageUpload;
+use crate::storage::jobs::ImageJob;
+use crate::storage::pipeline::delete_image_variants;
+use crate::storage::{Storage, StorageBackend};
+
+/// 정리 주기
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// 만료 후 이 시간이 지난 PENDING 업로드 삭제 (만료 직전에 시작한 확정 처리와 겹치지 않도록)
+const PENDING_GRACE: Duration = Duration::from_secs(60 * 60);
+/// 확정 후 이 시간 동안 포스트에 연결되지 않은 READY 업로드 삭제
+const UNATTACHED_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// DEAD 작업의 원본 보관 기간 (이 기간 동안 관리자가 retryImageJobs로 재시도 가능)
+const DEAD_JOB_RETENTION: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+/// 한 번에 삭제할 최대 업로드 수
+const BATCH_SIZE: i64 = 100;
+
+/// 버려진 이미지 정리
+///
+/// 확정되지 않고 만료된 업로드의 원본, 확정 후 포스트에 쓰이지 않은 업로드의 변형,
+/// 보관 기간이 지난 DEAD 이미지 작업의 원본을 삭제 (원본에는 EXIF GPS 좌표가 남아 있음)
+pub fn start(pool: SqlitePool, storage: Storage) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sweep(&pool, &storage).await {
+                log::warn!("Failed to sweep image uploads: {}", e);
+            }
+        }
+    });
+}
+
+/// 정리 대상이 없어질 때까지 삭제
+async fn sweep(pool: &SqlitePool, storage: &Storage) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let pending_before = now - chrono::Duration::from_std(PENDING_GRACE).unwrap_or_else(|_| chrono::Duration::zero());
+    let ready_before = now - chrono::Duration::from_std(UNATTACHED_TTL).unwrap_or_else(|_| chrono::Duration::zero());
+    let dead_before = now - chrono::Duration::from_std(DEAD_JOB_RETENTION).unwrap_or_else(|_| chrono::Duration::zero());
+
+    // 행을 먼저 지우고 저장소에서 삭제 (같은 업로드를 포스트에 연결하는 요청과 겹치지 않도록)
+    loop {
+        let expired = ImageUpload::delete_expired_pending(pool, pending_before, BATCH_SIZE).await?;
+        for upload in &expired {
+            if let Err(e) = storage.delete(&upload.original_key).await {
+                log::warn!("Failed to delete expired upload {}: {}", upload.original_key, e);
+            }
+        }
+        if !expired.is_empty() {
+            log::info!("Removed {} expired image uploads", expired.len());
+        }
+        if (expired.len() as i64) < BATCH_SIZE {
+            break;
+        }
+    }
+
+    loop {
+        let unattached = ImageUpload::delete_unattached_ready(pool, ready_before, BATCH_SIZE).await?;
+        for upload in &unattached {
+            delete_image_variants(storage, &upload.user_id, &upload.id).await;
+        }
+        if !unattached.is_empty() {
+            log::info!("Removed {} unattached image uploads", unattached.len());
+        }
+        if (unattached.len() as i64) < BATCH_SIZE {
+            break;
+        }
+    }
+
+    loop {
+        let dead = ImageJob::delete_dead_before(pool, dead_before, BATCH_SIZE).await?;
+        for job in &dead {
+            if let Err(e) = storage.delete(&job.source_key).await {
+                log::warn!("Failed to delete original image {}: {}", job.source_key, e);
+            }
+        }
+        if !dead.is_empty() {
+            log::info!("Removed originals of {} dead image jobs", dead.len());
+        }
+        if (dead.len() as i64) < BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(())
+}
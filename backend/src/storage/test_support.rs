@@ -0,0 +1,47 @@
+use exif::experimental::Writer;
+use exif::{Field, In, Rational, Tag, Value};
+use std::io::Cursor;
+
+/// 테스트용 EXIF(TIFF) 블록 (방향, 촬영 시각, 서울시청 좌표)
+pub fn sample_exif(orientation: u16) -> Vec<u8> {
+    let field = |tag, value| Field { tag, ifd_num: In::PRIMARY, value };
+    let rationals = |values: [(u32, u32); 3]| Value::Rational(values.into_iter().map(Rational::from).collect());
+
+    let fields = [
+        field(Tag::Orientation, Value::Short(vec![orientation])),
+        field(Tag::DateTimeOriginal, Value::Ascii(vec![b"2024:05:01 12:30:15".to_vec()])),
+        field(Tag::GPSLatitudeRef, Value::Ascii(vec![b"N".to_vec()])),
+        field(Tag::GPSLatitude, rationals([(37, 1), (33, 1), (5880, 100)])),
+        field(Tag::GPSLongitudeRef, Value::Ascii(vec![b"E".to_vec()])),
+        field(Tag::GPSLongitude, rationals([(126, 1), (58, 1), (4680, 100)])),
+    ];
+
+    let mut writer = Writer::new();
+    for field in &fields {
+        writer.push_field(field);
+    }
+    let mut tiff = Cursor::new(Vec::new());
+    writer.write(&mut tiff, false).unwrap();
+    tiff.into_inner()
+}
+
+/// JPEG의 SOI 바로 뒤에 EXIF(APP1) 세그먼트 삽입
+pub fn with_exif(jpeg: &[u8], tiff: &[u8]) -> Vec<u8> {
+    let length = (2 + 6 + tiff.len()) as u16;
+    let mut data = jpeg[..2].to_vec();
+    data.extend_from_slice(&[0xFF, 0xE1]);
+    data.extend_from_slice(&length.to_be_bytes());
+    data.extend_from_slice(b"Exif\0\0");
+    data.extend_from_slice(tiff);
+    data.extend_from_slice(&jpeg[2..]);
+    data
+}
+
+/// 4x2 JPEG (EXIF 없음)
+pub fn sample_jpeg() -> Vec<u8> {
+    let mut jpeg = Vec::new();
+    image::DynamicImage::new_rgb8(4, 2)
+        .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .unwrap();
+    jpeg
+}
@@ -0,0 +1,105 @@
+use std::env;
+use std::io::Read;
+
+/// 이미지 업로드 최대 크기 기본값 (10MB)
+const DEFAULT_MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+/// 이미지 업로드 최대 크기 (MAX_IMAGE_UPLOAD_BYTES 환경 변수)
+pub fn max_image_bytes() -> usize {
+    env::var("MAX_IMAGE_UPLOAD_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_IMAGE_BYTES)
+}
+
+/// 업로드를 허용하는 이미지 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageMime {
+    Jpeg,
+    Png,
+    WebP,
+}
+
+impl ImageMime {
+    /// 파일 시그니처로 형식 판별 (클라이언트가 보낸 Content-Type은 신뢰하지 않음)
+    pub fn sniff(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            Some(Self::WebP)
+        } else {
+            None
+        }
+    }
+
+    /// 직접 업로드 요청의 Content-Type 확인 (업로드 후 시그니처로 다시 검증)
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.trim().to_ascii_lowercase().as_str() {
+            "image/jpeg" | "image/jpg" => Some(Self::Jpeg),
+            "image/png" => Some(Self::Png),
+            "image/webp" => Some(Self::WebP),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::WebP => "image/webp",
+        }
+    }
+}
+
+/// 크기와 형식 검증
+pub fn validate_image(data: &[u8], max_bytes: usize) -> Result<ImageMime, String> {
+    if data.len() > max_bytes {
+        return Err(format!("Image must be at most {} bytes", max_bytes));
+    }
+    ImageMime::sniff(data).ok_or_else(|| "Unsupported image type (JPEG, PNG, WebP only)".to_string())
+}
+
+/// 업로드 파일을 최대 크기 + 1바이트까지만 읽고 검증 (큰 파일을 끝까지 읽지 않음)
+pub fn read_image(reader: impl Read, max_bytes: usize) -> Result<(Vec<u8>, ImageMime), String> {
+    let mut data = Vec::new();
+    reader
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Failed to read upload: {}", e))?;
+
+    let mime = validate_image(&data, max_bytes)?;
+    Ok((data, mime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(ImageMime::sniff(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]), Some(ImageMime::Jpeg));
+        assert_eq!(ImageMime::sniff(b"\x89PNG\r\n\x1a\n\0\0"), Some(ImageMime::Png));
+        assert_eq!(ImageMime::sniff(b"RIFF\x10\0\0\0WEBPVP8 "), Some(ImageMime::WebP));
+        assert_eq!(ImageMime::sniff(b"RIFF\x10\0\0\0WAVEfmt "), None);
+        assert_eq!(ImageMime::sniff(b"<svg xmlns="), None);
+        assert_eq!(ImageMime::sniff(&[]), None);
+    }
+
+    #[test]
+    fn test_from_content_type() {
+        assert_eq!(ImageMime::from_content_type("Image/JPEG"), Some(ImageMime::Jpeg));
+        assert_eq!(ImageMime::from_content_type("image/webp"), Some(ImageMime::WebP));
+        assert_eq!(ImageMime::from_content_type("image/svg+xml"), None);
+    }
+
+    #[test]
+    fn test_read_image_limits() {
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+
+        assert_eq!(read_image(&jpeg[..], 6).unwrap(), (jpeg.to_vec(), ImageMime::Jpeg));
+        assert!(read_image(&jpeg[..], 5).unwrap_err().contains("at most 5 bytes"));
+        assert!(read_image(&b"GIF89a"[..], 100).unwrap_err().contains("Unsupported"));
+    }
+}
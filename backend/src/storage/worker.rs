@@ -0,0 +1,143 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::events::{AppEvent, EventBus};
+use crate::models::post::ImageUrls;
+use crate::models::{ImageStatus, PostImageStatus};
+use crate::storage::jobs::ImageJob;
+use crate::storage::pipeline::{delete_image_variants, upload_image_variants};
+use crate::storage::upload::max_image_bytes;
+use crate::storage::{Storage, StorageBackend};
+
+/// 깨우는 신호가 없어도 작업 큐를 확인하는 주기 (재시도, 다른 인스턴스의 기록)
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 동시에 처리할 작업 수 (변형 생성은 blocking 스레드 풀에서 실행)
+const CONCURRENCY: usize = 2;
+
+/// 이미지 처리 워커
+///
+/// 포스트는 원본만 저장소에 올리고 image_status = PROCESSING으로 바로 생성하고,
+/// 워커가 변형을 만든 뒤 READY로 바꾸고 postImageStatusChanged 구독으로 알림
+/// (실패 시 지수 백오프 후 재시도, 한도 초과 시 FAILED)
+#[derive(Clone)]
+pub struct ImageWorker {
+    pool: SqlitePool,
+    storage: Storage,
+    event_bus: EventBus,
+    wakeup: Arc<Notify>,
+}
+
+impl ImageWorker {
+    pub fn start(pool: SqlitePool, storage: Storage, event_bus: EventBus) -> Self {
+        let worker = Self {
+            pool,
+            storage,
+            event_bus,
+            wakeup: Arc::new(Notify::new()),
+        };
+        for _ in 0..CONCURRENCY {
+            tokio::spawn(run_worker(worker.clone()));
+        }
+        worker
+    }
+
+    /// 작업 기록이 커밋된 후 워커 깨우기
+    pub fn wake(&self) {
+        self.wakeup.notify_one();
+    }
+}
+
+async fn run_worker(worker: ImageWorker) {
+    loop {
+        let _ = tokio::time::timeout(POLL_INTERVAL, worker.wakeup.notified()).await;
+
+        loop {
+            let job = match ImageJob::claim_due(&worker.pool).await {
+                Ok(Some(job)) => job,
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Failed to read image jobs: {}", e);
+                    break;
+                }
+            };
+
+            handle_job(&worker, job).await;
+        }
+    }
+}
+
+/// 작업 처리 후 결과 기록 및 알림
+async fn handle_job(worker: &ImageWorker, job: ImageJob) {
+    let pool = &worker.pool;
+
+    match process_job(pool, &worker.storage, &job).await {
+        Ok(image_urls) => {
+            if let Err(e) = worker.storage.delete(&job.source_key).await {
+                log::warn!("Failed to delete original image {}: {}", job.source_key, e);
+            }
+            if let Err(e) = job.complete(pool).await {
+                log::warn!("Failed to complete image job {}: {}", job.id, e);
+            }
+            if let Some(image_urls) = image_urls {
+                publish_status(worker, &job, ImageStatus::Ready, Some(image_urls));
+            }
+        }
+        Err(error) => {
+            log::warn!("Image job {} (post {}) failed: {}", job.id, job.post_id, error);
+
+            match job.fail(pool, &error).await {
+                Ok(true) => {
+                    let result = sqlx::query("UPDATE feed_posts SET image_status = 'FAILED' WHERE id = ?")
+                        .bind(&job.post_id)
+                        .execute(pool)
+                        .await;
+                    if let Err(e) = result {
+                        log::warn!("Failed to mark post {} image as failed: {}", job.post_id, e);
+                    }
+                    publish_status(worker, &job, ImageStatus::Failed, None);
+                    // 원본은 재시도할 수 있도록 보관 기간 동안 남겨두고 sweeper가 삭제 (공개 라우트로는 제공하지 않음)
+                }
+                Ok(false) => {}
+                Err(e) => log::warn!("Failed to update image job {}: {}", job.id, e),
+            }
+        }
+    }
+}
+
+/// 원본으로 변형을 만들어 포스트에 기록 (처리 중 포스트가 삭제되었으면 변형을 지우고 None)
+async fn process_job(pool: &SqlitePool, storage: &Storage, job: &ImageJob) -> Result<Option<ImageUrls>, String> {
+    let image_data = storage
+        .download(&job.source_key, max_image_bytes())
+        .await
+        .map_err(|e| format!("Failed to read original image: {}", e))?;
+
+    let image_urls = upload_image_variants(storage, &job.user_id, &job.image_id, image_data).await?;
+    let image_urls_json = serde_json::to_string(&image_urls).map_err(|e| e.to_string())?;
+
+    let updated = sqlx::query(
+        "UPDATE feed_posts SET image_urls = ?, image_status = 'READY' WHERE id = ?"
+    )
+    .bind(&image_urls_json)
+    .bind(&job.post_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    if updated == 0 {
+        delete_image_variants(storage, &job.user_id, &job.image_id).await;
+        return Ok(None);
+    }
+
+    Ok(Some(image_urls))
+}
+
+fn publish_status(worker: &ImageWorker, job: &ImageJob, status: ImageStatus, image_urls: Option<ImageUrls>) {
+    worker.event_bus.publish(AppEvent::PostImageStatusChanged(PostImageStatus {
+        post_id: job.post_id.clone(),
+        status,
+        image_urls,
+    }));
+}